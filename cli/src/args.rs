@@ -0,0 +1,30 @@
+use ckb_types::{
+    packed::{CellInput, OutPoint},
+    prelude::*,
+};
+use time_cell_types::{type_id, IndexStateTypeArgs, InfoTypeCode};
+
+/// The index state type args of the out point which is consumed by the genesis transaction, and
+/// the owner lock hash requires the info type code
+pub fn index_state_args(
+    out_point: &OutPoint,
+    info_type_code: Option<InfoTypeCode>,
+    owner_lock_hash: Option<[u8; 32]>,
+) -> Vec<u8> {
+    let mut out_point_data = [0u8; 36];
+    out_point_data.copy_from_slice(out_point.as_slice());
+    IndexStateTypeArgs::new(out_point_data, info_type_code, owner_lock_hash).to_vec()
+}
+
+/// The index state type args of the standard Type ID, and the out point is the first input of
+/// the genesis transaction whose since is zero
+pub fn index_state_type_id_args(
+    first_input_out_point: &OutPoint,
+    output_index: u64,
+    info_type_code: Option<InfoTypeCode>,
+    owner_lock_hash: Option<[u8; 32]>,
+) -> Vec<u8> {
+    let first_input = CellInput::new(first_input_out_point.clone(), 0);
+    let type_id = type_id(first_input.as_slice(), output_index);
+    IndexStateTypeArgs::new_type_id(type_id, info_type_code, owner_lock_hash).to_vec()
+}
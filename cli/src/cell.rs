@@ -0,0 +1,113 @@
+use crate::error::Error;
+use std::fmt;
+use time_cell_types::{
+    rules::check_index_state, unpack_epoch, IndexStateData, TimeInfoData, TimeKind, TimeValue,
+    BLOCK_NUMBER_DATA_LEN, TIMESTAMP_DATA_LEN, VERSIONED_INFO_DATA_LEN,
+};
+
+/// The decoded data of the time index state cell or the time info cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeCell {
+    IndexState(IndexStateData),
+    Info(TimeInfoData),
+}
+
+impl TimeCell {
+    /// The time info cell data has the fixed lengths, and the lengths of the index state cell
+    /// data are different from them
+    pub fn from_data(data: &[u8]) -> Result<Self, Error> {
+        match data.len() {
+            TIMESTAMP_DATA_LEN | BLOCK_NUMBER_DATA_LEN | VERSIONED_INFO_DATA_LEN => {
+                Ok(TimeCell::Info(TimeInfoData::from_slice(data)?))
+            }
+            _ => Ok(TimeCell::IndexState(IndexStateData::from_slice(data)?)),
+        }
+    }
+}
+
+impl fmt::Display for TimeCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeCell::IndexState(index_state) => {
+                write!(
+                    f,
+                    "time index state: version {}, index {}, sum_of_time_info_cells {}",
+                    index_state.version, index_state.index, index_state.sum_of_time_info_cells
+                )?;
+                for updater in &index_state.updaters {
+                    write!(f, ", updater 0x{}", hex::encode(updater))?;
+                }
+                Ok(())
+            }
+            TimeCell::Info(info) => write!(
+                f,
+                "time info: version {}, index {}, {}",
+                info.version(),
+                info.index(),
+                TimeValueDisplay(info.value())
+            ),
+        }
+    }
+}
+
+/// The time value with its unit, such as "timestamp 1615365304 s"
+pub struct TimeValueDisplay(pub TimeValue);
+
+impl fmt::Display for TimeValueDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            TimeValue::Timestamp(timestamp) => write!(f, "timestamp {} s", timestamp),
+            TimeValue::TimestampMillis(timestamp) => write!(f, "timestamp {} ms", timestamp),
+            TimeValue::BlockNumber(block_number) => write!(f, "block number {}", block_number),
+            TimeValue::Epoch(epoch) => {
+                let (number, index, length) = unpack_epoch(epoch);
+                write!(f, "epoch {} {}/{}", number, index, length)
+            }
+        }
+    }
+}
+
+/// The legacy layout is used for the timestamp in seconds, or for the block number if
+/// is_legacy is true, otherwise the versioned layout is used
+pub fn encode_info(index: u8, time: TimeValue, is_legacy: bool) -> Result<Vec<u8>, Error> {
+    let info = match time {
+        TimeValue::Timestamp(timestamp) => TimeInfoData::Timestamp { index, timestamp },
+        TimeValue::BlockNumber(block_number) if is_legacy => TimeInfoData::BlockNumber {
+            index,
+            block_number,
+        },
+        _ if is_legacy => {
+            return Err(Error::InvalidArgument(
+                "the legacy layout only has the timestamp in seconds and the block number"
+                    .to_string(),
+            ))
+        }
+        TimeValue::TimestampMillis(time) => TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::Timestamp,
+            time,
+        },
+        TimeValue::BlockNumber(time) => TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::BlockNumber,
+            time,
+        },
+        TimeValue::Epoch(time) => TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::Epoch,
+            time,
+        },
+    };
+    // The data is decoded again to check the epoch
+    let data = info.to_vec();
+    TimeInfoData::from_slice(&data)?;
+    Ok(data)
+}
+
+/// The index must be in the non-empty ring, and the data is decoded again to check the updaters
+pub fn encode_index_state(index_state: &IndexStateData) -> Result<Vec<u8>, Error> {
+    check_index_state(index_state).map_err(|err| Error::InvalidArgument(err.to_string()))?;
+    let data = index_state.to_vec();
+    IndexStateData::from_slice(&data)?;
+    Ok(data)
+}
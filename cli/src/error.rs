@@ -0,0 +1,49 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    InvalidHex(String),
+    InvalidArgument(String),
+    // The mock transaction file can't be read or parsed
+    InvalidMockTx(String),
+    TimeCell(time_cell_types::Error),
+}
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        Error::TimeCell(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHex(message) => write!(f, "invalid hex: {}", message),
+            Error::InvalidArgument(message) => write!(f, "invalid argument: {}", message),
+            Error::InvalidMockTx(message) => write!(f, "invalid mock transaction: {}", message),
+            Error::TimeCell(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Decode the hex string with or without the 0x prefix
+pub fn decode_hex(hex_str: &str) -> Result<Vec<u8>, Error> {
+    let hex_str = hex_str.trim();
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    hex::decode(hex_str).map_err(|err| Error::InvalidHex(err.to_string()))
+}
+
+pub fn decode_hash(hex_str: &str) -> Result<[u8; 32], Error> {
+    let bytes = decode_hex(hex_str)?;
+    if bytes.len() != 32 {
+        return Err(Error::InvalidHex(format!(
+            "{} is not a 32 bytes hash",
+            hex_str
+        )));
+    }
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
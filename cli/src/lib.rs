@@ -0,0 +1,8 @@
+//! The library of ckb-time-cli, which decodes and encodes the time cell data, computes the index
+//! state type args and checks the mock transactions with the rules of the contracts.
+pub mod args;
+pub mod cell;
+mod error;
+pub mod report;
+
+pub use error::{decode_hash, decode_hex, Error};
@@ -0,0 +1,251 @@
+use ckb_standalone_debugger::transaction::{MockTransaction, ReprMockTransaction};
+use ckb_time_cli::{
+    args::{index_state_args, index_state_type_id_args},
+    cell::{encode_index_state, encode_info, TimeCell},
+    decode_hash, decode_hex,
+    report::check_mock_tx,
+    Error,
+};
+use ckb_types::{packed::OutPoint, prelude::*};
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use std::convert::TryFrom;
+use std::fs;
+use std::process;
+use time_cell_types::{IndexStateData, InfoTypeCode, TimeValue};
+
+fn main() {
+    let matches = App::new("ckb-time-cli")
+        .about("Inspect and build the cells of the CKB time oracle")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("decode")
+                .about("Decode the time index state or time info cell data")
+                .arg(Arg::with_name("data").required(true).help("Hex cell data")),
+        )
+        .subcommand(
+            SubCommand::with_name("tx")
+                .about("Decode the time cells of a mock transaction and check the contract rules")
+                .arg(
+                    Arg::with_name("file")
+                        .required(true)
+                        .help("Mock transaction json, such as the tx.json of the native setups"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("encode")
+                .about("Encode the time index state or time info cell data")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("index-state")
+                        .arg(number_arg("index", true))
+                        .arg(number_arg("sum", true))
+                        .arg(
+                            Arg::with_name("updater")
+                                .long("updater")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help("Lock hash of an updater"),
+                        )
+                        .arg(
+                            Arg::with_name("legacy")
+                                .long("legacy")
+                                .conflicts_with("updater")
+                                .help("Use the legacy layout without the version"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("info")
+                        .arg(number_arg("index", true))
+                        .arg(number_arg("timestamp", false).help("Timestamp in seconds"))
+                        .arg(
+                            number_arg("timestamp-millis", false).help("Timestamp in milliseconds"),
+                        )
+                        .arg(number_arg("block-number", false))
+                        .arg(number_arg("epoch", false).help("Epoch with fraction"))
+                        .group(
+                            ArgGroup::with_name("time")
+                                .args(&["timestamp", "timestamp-millis", "block-number", "epoch"])
+                                .required(true),
+                        )
+                        .arg(
+                            Arg::with_name("legacy")
+                                .long("legacy")
+                                .help("Use the legacy layout for the block number"),
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("index-state-args")
+                .about("Compute the index state type args from the out point of an input")
+                .arg(
+                    Arg::with_name("tx-hash")
+                        .long("tx-hash")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(number_arg("index", true))
+                .arg(
+                    Arg::with_name("info-code-hash")
+                        .long("info-code-hash")
+                        .takes_value(true)
+                        .requires("info-hash-type")
+                        .help("Code hash of the info type script"),
+                )
+                .arg(
+                    Arg::with_name("info-hash-type")
+                        .long("info-hash-type")
+                        .takes_value(true)
+                        .possible_values(&["data", "type"])
+                        .requires("info-code-hash")
+                        .help("Hash type of the info type script"),
+                )
+                .arg(
+                    Arg::with_name("owner")
+                        .long("owner")
+                        .takes_value(true)
+                        .requires("info-code-hash")
+                        .help("Lock hash of the owner"),
+                )
+                .arg(number_arg("type-id", false).help(
+                    "Output index of the index state cell, and the out point is the first input",
+                )),
+        )
+        .get_matches();
+
+    let result = match matches.subcommand() {
+        ("decode", Some(matches)) => decode(matches),
+        ("tx", Some(matches)) => check_tx(matches),
+        ("encode", Some(matches)) => match matches.subcommand() {
+            ("index-state", Some(matches)) => encode_index_state_data(matches),
+            ("info", Some(matches)) => encode_info_data(matches),
+            _ => unreachable!(),
+        },
+        ("index-state-args", Some(matches)) => compute_index_state_args(matches),
+        _ => unreachable!(),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn number_arg(name: &'static str, required: bool) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .long(name)
+        .takes_value(true)
+        .required(required)
+}
+
+/// The number is decimal or hex with the 0x prefix
+fn parse_number<T: TryFrom<u64>>(matches: &ArgMatches, name: &str) -> Result<T, Error> {
+    let value = matches.value_of(name).unwrap_or_default();
+    let number = match value.strip_prefix("0x") {
+        Some(hex_value) => u64::from_str_radix(hex_value, 16).ok(),
+        None => value.parse().ok(),
+    };
+    number
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| Error::InvalidArgument(format!("invalid {}: {}", name, value)))
+}
+
+fn decode(matches: &ArgMatches) -> Result<(), Error> {
+    let data = decode_hex(matches.value_of("data").unwrap_or_default())?;
+    println!("{}", TimeCell::from_data(&data)?);
+    Ok(())
+}
+
+fn check_tx(matches: &ArgMatches) -> Result<(), Error> {
+    let content = fs::read_to_string(matches.value_of("file").unwrap_or_default())
+        .map_err(|err| Error::InvalidMockTx(err.to_string()))?;
+    let repr_tx: ReprMockTransaction =
+        serde_json::from_str(&content).map_err(|err| Error::InvalidMockTx(err.to_string()))?;
+    let mock_tx: MockTransaction = repr_tx.into();
+    let report = check_mock_tx(&mock_tx);
+
+    for decoded_cell in &report.cells {
+        match &decoded_cell.cell {
+            Ok(cell) => println!("{}: {}", decoded_cell.location, cell),
+            Err(err) => println!("{}: {}", decoded_cell.location, err),
+        }
+    }
+    let mut is_valid = true;
+    for verdict in &report.verdicts {
+        let result = match &verdict.result {
+            Ok(_) => "ok".to_string(),
+            Err(violation) => {
+                is_valid = false;
+                format!("error: {}", violation)
+            }
+        };
+        println!(
+            "oracle 0x{} {}: {}",
+            hex::encode(verdict.index_state_type_hash),
+            verdict.action,
+            result
+        );
+    }
+    if !is_valid {
+        process::exit(2);
+    }
+    Ok(())
+}
+
+fn encode_index_state_data(matches: &ArgMatches) -> Result<(), Error> {
+    let index = parse_number(matches, "index")?;
+    let sum = parse_number(matches, "sum")?;
+    let index_state = if matches.is_present("legacy") {
+        IndexStateData::new(index, sum)
+    } else if let Some(updaters) = matches.values_of("updater") {
+        let updaters = updaters.map(decode_hash).collect::<Result<Vec<_>, _>>()?;
+        IndexStateData::new_with_updaters(index, sum, updaters)
+    } else {
+        IndexStateData::new_versioned(index, sum)
+    };
+    println!("0x{}", hex::encode(encode_index_state(&index_state)?));
+    Ok(())
+}
+
+fn encode_info_data(matches: &ArgMatches) -> Result<(), Error> {
+    let index = parse_number(matches, "index")?;
+    let time = if matches.is_present("timestamp") {
+        TimeValue::Timestamp(parse_number(matches, "timestamp")?)
+    } else if matches.is_present("timestamp-millis") {
+        TimeValue::TimestampMillis(parse_number(matches, "timestamp-millis")?)
+    } else if matches.is_present("block-number") {
+        TimeValue::BlockNumber(parse_number(matches, "block-number")?)
+    } else {
+        TimeValue::Epoch(parse_number(matches, "epoch")?)
+    };
+    let data = encode_info(index, time, matches.is_present("legacy"))?;
+    println!("0x{}", hex::encode(data));
+    Ok(())
+}
+
+fn compute_index_state_args(matches: &ArgMatches) -> Result<(), Error> {
+    let tx_hash = decode_hash(matches.value_of("tx-hash").unwrap_or_default())?;
+    let index: u32 = parse_number(matches, "index")?;
+    let out_point = OutPoint::new(tx_hash.pack(), index);
+    let info_type_code = match matches.value_of("info-code-hash") {
+        Some(code_hash) => {
+            let hash_type = match matches.value_of("info-hash-type") {
+                Some("type") => 1,
+                _ => 0,
+            };
+            Some(InfoTypeCode::new(decode_hash(code_hash)?, hash_type))
+        }
+        None => None,
+    };
+    let owner_lock_hash = match matches.value_of("owner") {
+        Some(owner) => Some(decode_hash(owner)?),
+        None => None,
+    };
+    let args = if matches.is_present("type-id") {
+        let output_index = parse_number(matches, "type-id")?;
+        index_state_type_id_args(&out_point, output_index, info_type_code, owner_lock_hash)
+    } else {
+        index_state_args(&out_point, info_type_code, owner_lock_hash)
+    };
+    println!("0x{}", hex::encode(args));
+    Ok(())
+}
@@ -0,0 +1,616 @@
+use crate::cell::TimeCell;
+use ckb_standalone_debugger::transaction::MockTransaction;
+use ckb_types::{
+    bytes::Bytes,
+    core::HeaderView,
+    packed::{CellInput, CellOutput, OutPoint},
+    prelude::*,
+};
+use std::fmt;
+use time_cell_types::{
+    rules::{self, RuleError},
+    type_id, IndexStateData, IndexStateId, IndexStateTypeArgs, InfoTypeArgs, InfoTypeCode,
+    TimeInfoData, TimeValue, INDEX_STATE_TYPE_HASH_LEN, MILLIS_PER_SECOND,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Input(usize),
+    Output(usize),
+    CellDep(usize),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Input(index) => write!(f, "input {}", index),
+            Location::Output(index) => write!(f, "output {}", index),
+            Location::CellDep(index) => write!(f, "cell dep {}", index),
+        }
+    }
+}
+
+/// The time cell of the mock transaction, or the error of decoding its data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCell {
+    pub location: Location,
+    pub cell: Result<TimeCell, time_cell_types::Error>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Genesis,
+    Update,
+    Migration,
+    Destroy,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let action = match self {
+            Action::Genesis => "genesis",
+            Action::Update => "update",
+            Action::Migration => "migration",
+            Action::Destroy => "destroy",
+        };
+        write!(f, "{}", action)
+    }
+}
+
+/// The rules of the index state type and info type scripts which are broken by the transaction.
+/// The rules of the decoded time cells are shared with the SDK, see `time_cell_types::rules`.
+/// The signatures of the operators and the locks are not verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    InvalidCellData(Location, time_cell_types::Error),
+    Rule(RuleError),
+    InvalidIndexStateArgs,
+    InvalidInfoTypeArgs,
+    IndexStateAmountError,
+    IndexStateNotFound,
+    UpdaterNotAuthorized,
+    OwnerNotAuthorized,
+    InfoAmountError,
+    InfoAmountNotSame,
+    InfoTypeArgsNotSame,
+    LatestTimeInfoNotExist,
+    InvalidSince,
+    HeaderDepMissing,
+    HeaderNotSame,
+    InfoCellsNotAllBurnt,
+    InfoCellsNotMigrated,
+    IndexStateOwnerNotExist,
+    UpdatersChanged,
+}
+
+impl From<RuleError> for Violation {
+    fn from(err: RuleError) -> Self {
+        Violation::Rule(err)
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::InvalidCellData(location, err) => write!(f, "{}: {}", location, err),
+            Violation::Rule(err) => write!(f, "{}", err),
+            Violation::InvalidIndexStateArgs => write!(f, "invalid time index state type args"),
+            Violation::InvalidInfoTypeArgs => write!(f, "invalid time info type args"),
+            Violation::IndexStateAmountError => {
+                write!(
+                    f,
+                    "more than one time index state cell in the inputs or outputs"
+                )
+            }
+            Violation::IndexStateNotFound => write!(f, "time index state cell not found"),
+            Violation::UpdaterNotAuthorized => write!(f, "updater not authorized"),
+            Violation::OwnerNotAuthorized => write!(f, "owner not authorized"),
+            Violation::InfoAmountError => write!(f, "invalid amount of time info cells"),
+            Violation::InfoAmountNotSame => {
+                write!(f, "sum of time info cells changed without the owner")
+            }
+            Violation::InfoTypeArgsNotSame => write!(f, "time info type args not same"),
+            Violation::LatestTimeInfoNotExist => write!(f, "latest time info cell dep not found"),
+            Violation::InvalidSince => write!(f, "since of the time info input is not the time"),
+            Violation::HeaderDepMissing => write!(f, "header dep missing"),
+            Violation::HeaderNotSame => write!(f, "time info is not the time of the header dep"),
+            Violation::InfoCellsNotAllBurnt => write!(f, "time info cells not all burnt"),
+            Violation::InfoCellsNotMigrated => write!(f, "time info cells not migrated"),
+            Violation::IndexStateOwnerNotExist => write!(f, "time index state cell has no owner"),
+            Violation::UpdatersChanged => write!(f, "updaters changed without the owner"),
+        }
+    }
+}
+
+/// The verdict of the time index state cell and the time info cells of one oracle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleVerdict {
+    pub index_state_type_hash: [u8; 32],
+    pub action: Action,
+    pub result: Result<(), Violation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub cells: Vec<DecodedCell>,
+    pub verdicts: Vec<OracleVerdict>,
+}
+
+struct TxCell {
+    location: Location,
+    output: CellOutput,
+    data: Bytes,
+    since: u64,
+}
+
+impl TxCell {
+    fn type_hash(&self) -> Option<[u8; 32]> {
+        self.output.type_().to_opt().map(|type_script| {
+            let mut type_hash = [0u8; 32];
+            type_hash.copy_from_slice(type_script.calc_script_hash().as_slice());
+            type_hash
+        })
+    }
+
+    fn type_args(&self) -> Bytes {
+        match self.output.type_().to_opt() {
+            Some(type_script) => type_script.args().unpack(),
+            None => Bytes::new(),
+        }
+    }
+
+    fn is_index_state_of(&self, index_state_type_hash: &[u8; 32]) -> bool {
+        self.type_hash().as_ref() == Some(index_state_type_hash)
+    }
+
+    // The type args of the time info cells start with the index state type hash
+    fn is_info_of(&self, index_state_type_hash: &[u8; 32]) -> bool {
+        let type_args = self.type_args();
+        type_args.len() >= INDEX_STATE_TYPE_HASH_LEN
+            && type_args[..INDEX_STATE_TYPE_HASH_LEN] == index_state_type_hash[..]
+    }
+
+    // The time info cells of the index state type args with the info type code must have that
+    // code, and any cells with the same type args are the time info cells of the legacy args
+    fn is_info_with_code(&self, info_type_code: Option<&InfoTypeCode>) -> bool {
+        match (self.output.type_().to_opt(), info_type_code) {
+            (Some(type_script), Some(info_type_code)) => info_type_code.is_code_of(
+                type_script.code_hash().as_slice(),
+                type_script.hash_type().as_slice()[0],
+            ),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    fn index_state(&self) -> Result<IndexStateData, Violation> {
+        IndexStateData::from_slice(&self.data)
+            .map_err(|err| Violation::InvalidCellData(self.location, err))
+    }
+
+    fn info(&self) -> Result<TimeInfoData, Violation> {
+        TimeInfoData::from_slice(&self.data)
+            .map_err(|err| Violation::InvalidCellData(self.location, err))
+    }
+}
+
+// The cells of an oracle in the inputs and outputs of the transaction
+struct OracleCells<'a> {
+    index_state_inputs: Vec<&'a TxCell>,
+    index_state_outputs: Vec<&'a TxCell>,
+    info_inputs: Vec<&'a TxCell>,
+    info_outputs: Vec<&'a TxCell>,
+}
+
+struct MockTx {
+    inputs: Vec<TxCell>,
+    input_out_points: Vec<OutPoint>,
+    input_lock_hashes: Vec<[u8; 32]>,
+    outputs: Vec<TxCell>,
+    cell_deps: Vec<TxCell>,
+    header_deps: Vec<HeaderView>,
+}
+
+/// Decode the time cells of the mock transaction and check the rules of the contracts for every
+/// oracle whose cells are consumed or created by the transaction. The time index state cells
+/// are found by the type args of the time info cells.
+pub fn check_mock_tx(mock_tx: &MockTransaction) -> Report {
+    let tx = load_mock_tx(mock_tx);
+    let all_cells: Vec<&TxCell> = tx
+        .inputs
+        .iter()
+        .chain(tx.outputs.iter())
+        .chain(tx.cell_deps.iter())
+        .collect();
+
+    let mut index_state_type_hashes: Vec<[u8; 32]> = vec![];
+    for cell in &all_cells {
+        let type_args = cell.type_args();
+        if type_args.len() < INDEX_STATE_TYPE_HASH_LEN
+            || TimeInfoData::from_slice(&cell.data).is_err()
+        {
+            continue;
+        }
+        let mut type_hash = [0u8; 32];
+        type_hash.copy_from_slice(&type_args[..INDEX_STATE_TYPE_HASH_LEN]);
+        let is_index_state = all_cells.iter().any(|index_state_cell| {
+            index_state_cell.is_index_state_of(&type_hash)
+                && IndexStateData::from_slice(&index_state_cell.data).is_ok()
+        });
+        if is_index_state && !index_state_type_hashes.contains(&type_hash) {
+            index_state_type_hashes.push(type_hash);
+        }
+    }
+
+    let cells = all_cells
+        .iter()
+        .filter_map(|cell| {
+            let cell_result = if index_state_type_hashes
+                .iter()
+                .any(|type_hash| cell.is_index_state_of(type_hash))
+            {
+                IndexStateData::from_slice(&cell.data).map(TimeCell::IndexState)
+            } else if index_state_type_hashes
+                .iter()
+                .any(|type_hash| cell.is_info_of(type_hash))
+            {
+                TimeInfoData::from_slice(&cell.data).map(TimeCell::Info)
+            } else {
+                return None;
+            };
+            Some(DecodedCell {
+                location: cell.location,
+                cell: cell_result,
+            })
+        })
+        .collect();
+
+    let verdicts = index_state_type_hashes
+        .iter()
+        .filter_map(|type_hash| check_oracle(&tx, type_hash))
+        .collect();
+    Report { cells, verdicts }
+}
+
+fn load_mock_tx(mock_tx: &MockTransaction) -> MockTx {
+    let inputs = mock_tx
+        .mock_info
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, mock_input)| TxCell {
+            location: Location::Input(index),
+            output: mock_input.output.clone(),
+            data: mock_input.data.clone(),
+            since: mock_input.input.since().unpack(),
+        })
+        .collect();
+    let input_out_points = mock_tx
+        .mock_info
+        .inputs
+        .iter()
+        .map(|mock_input| mock_input.input.previous_output())
+        .collect();
+    let input_lock_hashes = mock_tx
+        .mock_info
+        .inputs
+        .iter()
+        .map(|mock_input| {
+            let mut lock_hash = [0u8; 32];
+            lock_hash.copy_from_slice(mock_input.output.lock().calc_script_hash().as_slice());
+            lock_hash
+        })
+        .collect();
+    let raw_tx = mock_tx.tx.raw();
+    let outputs = raw_tx
+        .outputs()
+        .into_iter()
+        .zip(raw_tx.outputs_data().into_iter())
+        .enumerate()
+        .map(|(index, (output, data))| TxCell {
+            location: Location::Output(index),
+            output,
+            data: data.raw_data(),
+            since: 0,
+        })
+        .collect();
+    let cell_deps = mock_tx
+        .mock_info
+        .cell_deps
+        .iter()
+        .enumerate()
+        .map(|(index, mock_cell_dep)| TxCell {
+            location: Location::CellDep(index),
+            output: mock_cell_dep.output.clone(),
+            data: mock_cell_dep.data.clone(),
+            since: 0,
+        })
+        .collect();
+    MockTx {
+        inputs,
+        input_out_points,
+        input_lock_hashes,
+        outputs,
+        cell_deps,
+        header_deps: mock_tx.mock_info.header_deps.clone(),
+    }
+}
+
+// The oracle is skipped if its cells are only in the cell deps
+fn check_oracle(tx: &MockTx, index_state_type_hash: &[u8; 32]) -> Option<OracleVerdict> {
+    let info_type_code = tx
+        .inputs
+        .iter()
+        .chain(tx.outputs.iter())
+        .find(|cell| cell.is_index_state_of(index_state_type_hash))
+        .and_then(|cell| load_index_state_args(cell).ok())
+        .and_then(|index_state_args| index_state_args.info_type_code);
+    let is_info = |cell: &TxCell| {
+        cell.is_info_of(index_state_type_hash) && cell.is_info_with_code(info_type_code.as_ref())
+    };
+    let cells = OracleCells {
+        index_state_inputs: filter_cells(&tx.inputs, |cell| {
+            cell.is_index_state_of(index_state_type_hash)
+        }),
+        index_state_outputs: filter_cells(&tx.outputs, |cell| {
+            cell.is_index_state_of(index_state_type_hash)
+        }),
+        info_inputs: filter_cells(&tx.inputs, is_info),
+        info_outputs: filter_cells(&tx.outputs, is_info),
+    };
+
+    let (action, result) = match (
+        cells.index_state_inputs.len(),
+        cells.index_state_outputs.len(),
+    ) {
+        (0, 0) if cells.info_inputs.is_empty() && cells.info_outputs.is_empty() => return None,
+        // No time info cell can be destroyed without the time index state cell
+        (0, 0) if cells.info_outputs.is_empty() => {
+            (Action::Destroy, Err(Violation::IndexStateNotFound))
+        }
+        (0, 0) => (Action::Update, Err(Violation::IndexStateNotFound)),
+        (0, 1) => (
+            Action::Genesis,
+            check_genesis(tx, &cells, index_state_type_hash),
+        ),
+        (1, 1) => match is_migration(&cells) {
+            Ok(true) => (Action::Migration, check_migration(tx, &cells)),
+            Ok(false) => (Action::Update, check_update(tx, &cells)),
+            Err(violation) => (Action::Update, Err(violation)),
+        },
+        (1, 0) => (Action::Destroy, check_destroy(tx, &cells)),
+        (_, outputs_count) => {
+            let action = if outputs_count == 0 {
+                Action::Destroy
+            } else {
+                Action::Update
+            };
+            (action, Err(Violation::IndexStateAmountError))
+        }
+    };
+    Some(OracleVerdict {
+        index_state_type_hash: *index_state_type_hash,
+        action,
+        result,
+    })
+}
+
+fn filter_cells<F>(cells: &[TxCell], predicate: F) -> Vec<&TxCell>
+where
+    F: Fn(&TxCell) -> bool,
+{
+    cells.iter().filter(|cell| predicate(cell)).collect()
+}
+
+fn is_migration(cells: &OracleCells) -> Result<bool, Violation> {
+    let input_index_state = cells.index_state_inputs[0].index_state()?;
+    let output_index_state = cells.index_state_outputs[0].index_state()?;
+    Ok(input_index_state.sum_of_time_info_cells != output_index_state.sum_of_time_info_cells)
+}
+
+fn load_index_state_args(cell: &TxCell) -> Result<IndexStateTypeArgs, Violation> {
+    IndexStateTypeArgs::from_slice(&cell.type_args()).map_err(|_| Violation::InvalidIndexStateArgs)
+}
+
+fn check_index_state(cell: &TxCell) -> Result<IndexStateData, Violation> {
+    let index_state = cell.index_state()?;
+    rules::check_index_state(&index_state)?;
+    Ok(index_state)
+}
+
+fn is_authorized(tx: &MockTx, lock_hash: &[u8; 32]) -> bool {
+    tx.input_lock_hashes
+        .iter()
+        .any(|input_lock_hash| input_lock_hash == lock_hash)
+}
+
+// Every slot in [start, end) must be filled by exactly one time info, and the time info cells
+// must share the same type script, otherwise the cells of other info type args could take the
+// slots of the ring
+fn check_slots(infos: &[&TxCell], start: u8, end: u8) -> Result<(), Violation> {
+    if let Some(first_cell) = infos.first() {
+        let info_type = first_cell.output.type_();
+        if infos
+            .iter()
+            .any(|cell| cell.output.type_().as_slice() != info_type.as_slice())
+        {
+            return Err(Violation::InfoTypeArgsNotSame);
+        }
+    }
+    let indexes = infos
+        .iter()
+        .map(|cell| cell.info().map(|info| info.index()))
+        .collect::<Result<Vec<_>, _>>()?;
+    rules::check_info_slots(indexes, start, end).map_err(Violation::from)
+}
+
+// The latest time info is the time info cell of the current index of the input time index state
+// cell. It's the time info input itself only if the ring has one slot, otherwise it must be a
+// cell dep with the same info type script.
+fn load_latest_info(
+    tx: &MockTx,
+    info_cell: &TxCell,
+    input_info: &TimeInfoData,
+    latest_index: u8,
+) -> Result<TimeInfoData, Violation> {
+    if input_info.index() == latest_index {
+        return Ok(*input_info);
+    }
+    let info_type_hash = info_cell.type_hash();
+    for cell in tx
+        .cell_deps
+        .iter()
+        .filter(|cell| cell.type_hash() == info_type_hash)
+    {
+        let info = cell.info()?;
+        if info.index() == latest_index {
+            return Ok(info);
+        }
+    }
+    Err(Violation::LatestTimeInfoNotExist)
+}
+
+fn check_genesis(
+    tx: &MockTx,
+    cells: &OracleCells,
+    index_state_type_hash: &[u8; 32],
+) -> Result<(), Violation> {
+    let index_state_cell = cells.index_state_outputs[0];
+    let is_args_valid = match load_index_state_args(index_state_cell)?.id {
+        IndexStateId::OutPoint(out_point) => tx
+            .input_out_points
+            .iter()
+            .any(|input_out_point| input_out_point.as_slice() == &out_point[..]),
+        IndexStateId::TypeId(args_type_id) => {
+            let output_index = tx
+                .outputs
+                .iter()
+                .position(|cell| cell.is_index_state_of(index_state_type_hash))
+                .unwrap_or_default();
+            match tx.input_out_points.first() {
+                Some(first_out_point) => {
+                    let first_input = CellInput::new_builder()
+                        .previous_output(first_out_point.clone())
+                        .since(tx.inputs[0].since.pack())
+                        .build();
+                    type_id(first_input.as_slice(), output_index as u64) == args_type_id
+                }
+                None => false,
+            }
+        }
+    };
+    if !is_args_valid {
+        return Err(Violation::InvalidIndexStateArgs);
+    }
+
+    let index_state = check_index_state(index_state_cell)?;
+    if !cells.info_inputs.is_empty() {
+        return Err(Violation::InfoAmountError);
+    }
+    check_slots(&cells.info_outputs, 0, index_state.sum_of_time_info_cells)
+}
+
+fn check_update(tx: &MockTx, cells: &OracleCells) -> Result<(), Violation> {
+    let input_index_state = cells.index_state_inputs[0].index_state()?;
+    let output_index_state = cells.index_state_outputs[0].index_state()?;
+    rules::check_index_state_updated(&input_index_state, &output_index_state)?;
+    if !input_index_state.updaters.is_empty()
+        && !tx
+            .input_lock_hashes
+            .iter()
+            .any(|lock_hash| input_index_state.is_updater_authorized(lock_hash))
+    {
+        return Err(Violation::UpdaterNotAuthorized);
+    }
+    // Only the owner can change the updaters, and the cells without an owner keep them forever
+    if input_index_state.updaters != output_index_state.updaters {
+        let owner_lock_hash = load_index_state_args(cells.index_state_inputs[0])?
+            .owner_lock_hash
+            .ok_or(Violation::UpdatersChanged)?;
+        if !is_authorized(tx, &owner_lock_hash) {
+            return Err(Violation::OwnerNotAuthorized);
+        }
+    }
+
+    if cells.info_inputs.len() != 1 || cells.info_outputs.len() != 1 {
+        return Err(Violation::InfoAmountError);
+    }
+    let input_info_cell = cells.info_inputs[0];
+    let output_info_cell = cells.info_outputs[0];
+    let input_info = input_info_cell.info()?;
+    let output_info = output_info_cell.info()?;
+    let info_type_args = InfoTypeArgs::from_slice(&output_info_cell.type_args())
+        .map_err(|_| Violation::InvalidInfoTypeArgs)?;
+
+    rules::check_info_updated(&input_info, &output_info, &output_index_state)?;
+    let latest_info = load_latest_info(tx, input_info_cell, &input_info, input_index_state.index)?;
+    rules::check_info_time(&output_info, &latest_info, &info_type_args)?;
+
+    let output_value = output_info.value();
+    if info_type_args.has_header_dep_proof() {
+        let header = tx.header_deps.first().ok_or(Violation::HeaderDepMissing)?;
+        let is_same = match output_value {
+            TimeValue::Timestamp(timestamp) => {
+                timestamp as u64 == header.timestamp() / MILLIS_PER_SECOND
+            }
+            TimeValue::TimestampMillis(timestamp) => timestamp == header.timestamp(),
+            TimeValue::BlockNumber(block_number) => block_number == header.number(),
+            TimeValue::Epoch(epoch) => epoch == header.epoch().full_value(),
+        };
+        if !is_same {
+            return Err(Violation::HeaderNotSame);
+        }
+    } else if input_info_cell.since != output_value.since() {
+        return Err(Violation::InvalidSince);
+    }
+    Ok(())
+}
+
+// The ring grows with the time info cells of the new slots, or shrinks with the time info cells
+// of the removed slots, and only the owner can migrate the ring
+fn check_migration(tx: &MockTx, cells: &OracleCells) -> Result<(), Violation> {
+    let owner_lock_hash = load_index_state_args(cells.index_state_inputs[0])?
+        .owner_lock_hash
+        .ok_or(Violation::InfoAmountNotSame)?;
+    if !is_authorized(tx, &owner_lock_hash) {
+        return Err(Violation::OwnerNotAuthorized);
+    }
+    let input_index_state = cells.index_state_inputs[0].index_state()?;
+    let output_index_state = cells.index_state_outputs[0].index_state()?;
+    rules::check_index_state_migrated(&input_index_state, &output_index_state)?;
+
+    let input_sum = input_index_state.sum_of_time_info_cells;
+    let output_sum = output_index_state.sum_of_time_info_cells;
+    if output_sum > input_sum {
+        if !cells.info_inputs.is_empty() {
+            return Err(Violation::InfoCellsNotMigrated);
+        }
+        check_slots(&cells.info_outputs, input_sum, output_sum)
+    } else {
+        if !cells.info_outputs.is_empty() {
+            return Err(Violation::InfoCellsNotMigrated);
+        }
+        check_slots(&cells.info_inputs, output_sum, input_sum)
+    }
+}
+
+// The time index state cell without an owner keeps the legacy destroy rules, but its time info
+// cells can't be burnt
+fn check_destroy(tx: &MockTx, cells: &OracleCells) -> Result<(), Violation> {
+    let owner_lock_hash = match load_index_state_args(cells.index_state_inputs[0])?.owner_lock_hash
+    {
+        Some(owner_lock_hash) => owner_lock_hash,
+        None if cells.info_inputs.is_empty() => return Ok(()),
+        None => return Err(Violation::IndexStateOwnerNotExist),
+    };
+    if !is_authorized(tx, &owner_lock_hash) {
+        return Err(Violation::OwnerNotAuthorized);
+    }
+    let index_state = check_index_state(cells.index_state_inputs[0])?;
+    if !cells.info_outputs.is_empty()
+        || cells.info_inputs.len() != index_state.sum_of_time_info_cells as usize
+    {
+        return Err(Violation::InfoCellsNotAllBurnt);
+    }
+    Ok(())
+}
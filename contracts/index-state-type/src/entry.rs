@@ -3,45 +3,70 @@ use alloc::vec::Vec;
 use ckb_std::{
     ckb_constants::Source,
     ckb_types::{bytes::Bytes, packed::*, prelude::*},
-    high_level::{load_cell_data, load_cell_type, load_input_out_point, load_script, QueryIter},
+    high_level::{
+        load_cell, load_cell_data, load_cell_lock_hash, load_cell_type, load_cell_type_hash,
+        load_input, load_input_out_point, load_script, load_script_hash, QueryIter,
+    },
 };
 use core::result::Result;
-
-const SUM_OF_TIME_INFO_CELLS: u8 = 12;
-const INDEX_STATE_CELL_DATA_LEN: usize = 2;
+use time_cell_types::{
+    rules::{
+        check_index_state, check_index_state_migrated, check_index_state_updated, check_info_slots,
+        RuleError,
+    },
+    type_id, IndexStateData, IndexStateId, IndexStateTypeArgs, InfoTypeCode, TimeInfoData,
+    INDEX_STATE_TYPE_HASH_LEN,
+};
 
 pub fn main() -> Result<(), Error> {
-    if !check_type_script_exists_in_inputs()? {
+    // Every transaction can only create, update or destroy one time index state cell
+    let group_inputs_count = QueryIter::new(load_cell, Source::GroupInput).count();
+    if group_inputs_count > 1 {
+        return Err(Error::IndexStateInputsAmountError);
+    }
+    let group_outputs_count = QueryIter::new(load_cell, Source::GroupOutput).count();
+    if group_outputs_count > 1 {
+        return Err(Error::IndexStateOutputsAmountError);
+    }
+
+    match (group_inputs_count, group_outputs_count) {
         // Create the time index state cell and the input type script doesn't exist
-        load_output_type_script(|output_type_script| {
-            let out_point = load_input_out_point(0, Source::Input)?;
+        (0, _) => load_output_type_script(|output_type_script| {
+            // The out point of the args must be equal to the out point of one of the inputs, so
+            // several time index state cells can be created in one transaction with different
+            // inputs, or the Type ID of the args must be derived from the first input and the
+            // index of the output
             let type_args: Bytes = output_type_script.args().unpack();
-            if &type_args[..] != out_point.as_slice() {
+            let is_args_valid = match IndexStateTypeArgs::from_slice(&type_args)?.id {
+                IndexStateId::OutPoint(args_out_point) => {
+                    QueryIter::new(load_input_out_point, Source::Input)
+                        .any(|out_point| &args_out_point[..] == out_point.as_slice())
+                }
+                IndexStateId::TypeId(args_type_id) => args_type_id == calc_type_id()?,
+            };
+            if !is_args_valid {
                 return Err(Error::InvalidArgument);
             }
-            let _ = check_index_state_cell_data(Source::GroupOutput)?;
-            Ok(())
-        })
-    } else {
+            let index_state = check_index_state_cell_data(Source::GroupOutput)?;
+            check_info_cells_created(&index_state)
+        }),
         // Update the time index state cell and the type scripts of input and output exist
-        match check_cells_type_scripts_valid() {
-            Ok(_) => check_index_state_cells_data(),
-            Err(err) => Err(err),
-        }
+        (_, 1) => check_index_state_cells_data(),
+        // Destroy the time index state cell and the output type script doesn't exist
+        _ => check_index_state_cell_burnt(),
     }
 }
 
-fn check_type_script_exists_in_inputs() -> Result<bool, Error> {
-    let script = load_script()?;
-    let type_script_exists_in_inputs = QueryIter::new(load_cell_type, Source::Input).any(
-        |type_script_opt| match type_script_opt {
-            Some(type_script) => {
-                type_script.code_hash().raw_data()[..] == script.code_hash().raw_data()[..]
-            }
-            None => false,
-        },
-    );
-    Ok(type_script_exists_in_inputs)
+// The standard Type ID: blake2b(the first input | the index of the index state output)
+fn calc_type_id() -> Result<[u8; 32], Error> {
+    let first_input = load_input(0, Source::Input)?;
+    let index_state_type_hash = load_script_hash()?;
+    let output_index = QueryIter::new(load_cell_type_hash, Source::Output)
+        .position(|type_hash_opt| type_hash_opt == Some(index_state_type_hash));
+    match output_index {
+        Some(output_index) => Ok(type_id(first_input.as_slice(), output_index as u64)),
+        None => Err(Error::IndexStateTypeNotExist),
+    }
 }
 
 fn load_output_type_script<F>(closure: F) -> Result<(), Error>
@@ -55,38 +80,243 @@ where
     }
 }
 
-// Time index state cell data: index(u8) | sum_of_time_info_cells(u8)
-fn check_index_state_cell_data(source: Source) -> Result<Vec<u8>, Error> {
+// Time index state cell data has three layouts:
+// legacy: index(u8) | sum_of_time_info_cells(u8)
+// version 1: version(u8) | index(u8) | sum_of_time_info_cells(u8)
+// version 2: version(u8) | UpdatersIndexStateCellData(index, sum_of_time_info_cells, updaters)
+// The sum_of_time_info_cells is chosen when the cell is created and can only be changed by the
+// ring-size migration of the owner
+fn check_index_state_cell_data(source: Source) -> Result<IndexStateData, Error> {
     let data = load_cell_data(0, source)?;
-    if data.len() != INDEX_STATE_CELL_DATA_LEN {
-        return Err(Error::IndexStateDataLenError);
+    let index_state = IndexStateData::from_slice(&data)?;
+    check_index_state(&index_state)?;
+    Ok(index_state)
+}
+
+// The whole ring of the time info cells is created together with the time index state cell, so
+// the index can't point to a slot which doesn't exist. The data of the created time info cells is
+// checked by the info type script, so only the slots of the decoded ones are compared here.
+fn check_info_cells_created(index_state: &IndexStateData) -> Result<(), Error> {
+    let info_type_code = load_index_state_type_args()?.info_type_code;
+    let index_state_type_hash = load_script_hash()?;
+    let mut info_cells_count = 0;
+    let mut indexes = Vec::new();
+    for position in info_cell_positions(
+        &index_state_type_hash,
+        info_type_code.as_ref(),
+        Source::Output,
+    ) {
+        info_cells_count += 1;
+        let info_data = load_cell_data(position, Source::Output)?;
+        if let Ok(info) = TimeInfoData::from_slice(&info_data) {
+            indexes.push(info.index());
+        }
     }
-    if data[0] >= SUM_OF_TIME_INFO_CELLS {
-        return Err(Error::TimeIndexOutOfBound);
+    if info_cells_count != index_state.sum_of_time_info_cells as usize {
+        return Err(Error::TimeInfoCellsNotCreated);
     }
-    if data[1] != SUM_OF_TIME_INFO_CELLS {
-        return Err(Error::TimeInfoAmountError);
+    let decoded_count = indexes.len();
+    match check_info_slots(indexes, 0, index_state.sum_of_time_info_cells) {
+        Ok(()) => Ok(()),
+        // The slots of the time info cells which can't be decoded are left to the info type script
+        Err(RuleError::TimeInfoSlotMissing) if decoded_count < info_cells_count => Ok(()),
+        Err(_) => Err(Error::TimeInfoCellsNotCreated),
     }
-    Ok(data)
 }
 
+// The rules of the decoded cells are shared with the SDK and the CLI, see
+// `time_cell_types::rules`
 fn check_index_state_cells_data() -> Result<(), Error> {
-    let input_data = check_index_state_cell_data(Source::GroupInput)?;
-    let output_data = check_index_state_cell_data(Source::GroupOutput)?;
-    if input_data[0] == SUM_OF_TIME_INFO_CELLS - 1 {
-        if output_data[0] != 0 {
-            return Err(Error::TimeIndexIncreaseError);
+    let input_index_state = check_index_state_cell_data(Source::GroupInput)?;
+    let output_index_state = check_index_state_cell_data(Source::GroupOutput)?;
+    if input_index_state.sum_of_time_info_cells != output_index_state.sum_of_time_info_cells {
+        return check_index_state_cells_migrated(&input_index_state, &output_index_state);
+    }
+    check_index_state_updated(&input_index_state, &output_index_state)?;
+    check_updater_authorized(&input_index_state)?;
+    check_updaters_kept(&input_index_state, &output_index_state)?;
+    check_info_cell_updated(&output_index_state)
+}
+
+// The index can only be moved together with the update of the time info cell of the new index,
+// otherwise the index would point to a stale slot of the ring. Exactly one time info cell is
+// consumed and created, and the data of the created one is checked by the info type script, so
+// its index is compared here only if the data can be decoded.
+fn check_info_cell_updated(output_index_state: &IndexStateData) -> Result<(), Error> {
+    let info_type_code = load_index_state_type_args()?.info_type_code;
+    let index_state_type_hash = load_script_hash()?;
+    let mut info_input_positions = info_cell_positions(
+        &index_state_type_hash,
+        info_type_code.as_ref(),
+        Source::Input,
+    );
+    let mut info_output_positions = info_cell_positions(
+        &index_state_type_hash,
+        info_type_code.as_ref(),
+        Source::Output,
+    );
+    let info_output_position = match (
+        info_input_positions.next(),
+        info_input_positions.next(),
+        info_output_positions.next(),
+        info_output_positions.next(),
+    ) {
+        (Some(_), None, Some(info_output_position), None) => info_output_position,
+        _ => return Err(Error::TimeInfoNotUpdated),
+    };
+
+    let info_data = load_cell_data(info_output_position, Source::Output)?;
+    if let Ok(info) = TimeInfoData::from_slice(&info_data) {
+        if info.index() != output_index_state.index {
+            return Err(Error::TimeInfoIndexNotSame);
         }
-    } else if input_data[0] + 1 != output_data[0] {
-        return Err(Error::TimeIndexIncreaseError);
     }
     Ok(())
 }
 
-fn check_cells_type_scripts_valid() -> Result<(), Error> {
-    load_output_type_script(|_| match load_cell_type(0, Source::GroupInput) {
-        Ok(Some(_)) => Ok(()),
-        Ok(None) => Err(Error::IndexStateTypeNotExist),
-        Err(_) => Err(Error::IndexStateTypeNotExist),
-    })
+// The updaters of the input index state cell authorize the update. The time info cells can only be
+// updated together with the index state cell, so they are guarded by the updaters too.
+fn check_updater_authorized(index_state: &IndexStateData) -> Result<(), Error> {
+    if index_state.updaters.is_empty() {
+        return Ok(());
+    }
+    let is_authorized = QueryIter::new(load_cell_lock_hash, Source::Input)
+        .any(|lock_hash| index_state.is_updater_authorized(&lock_hash));
+    if !is_authorized {
+        return Err(Error::UpdaterNotAuthorized);
+    }
+    Ok(())
+}
+
+// The updaters can only be changed by the owner, otherwise any updater could take the oracle over
+// by replacing the others, and everyone could take over the cells without updaters. The cells
+// without an owner keep their updaters forever.
+fn check_updaters_kept(
+    input_index_state: &IndexStateData,
+    output_index_state: &IndexStateData,
+) -> Result<(), Error> {
+    if input_index_state.updaters == output_index_state.updaters {
+        return Ok(());
+    }
+    match load_index_state_type_args()?.owner_lock_hash {
+        Some(owner_lock_hash) => check_owner_authorized(&owner_lock_hash),
+        None => Err(Error::UpdatersChanged),
+    }
+}
+
+// The owner of the time index state cell can grow or shrink the ring of the time info cells
+// without changing the type args. The index is kept, the new time info cells are created when the
+// ring grows and the removed time info cells are burnt when the ring shrinks, so the ring is
+// always full after the migration. The cells without an owner can't be migrated.
+fn check_index_state_cells_migrated(
+    input_index_state: &IndexStateData,
+    output_index_state: &IndexStateData,
+) -> Result<(), Error> {
+    let index_state_type_args = load_index_state_type_args()?;
+    let info_type_code = match (
+        index_state_type_args.owner_lock_hash,
+        index_state_type_args.info_type_code,
+    ) {
+        (Some(owner_lock_hash), Some(info_type_code)) => {
+            check_owner_authorized(&owner_lock_hash)?;
+            info_type_code
+        }
+        _ => return Err(Error::TimeInfoAmountNotSame),
+    };
+    check_index_state_migrated(input_index_state, output_index_state)?;
+
+    let input_sum = input_index_state.sum_of_time_info_cells as usize;
+    let output_sum = output_index_state.sum_of_time_info_cells as usize;
+    let expected_counts = if output_sum > input_sum {
+        (0, output_sum - input_sum)
+    } else {
+        (input_sum - output_sum, 0)
+    };
+    let index_state_type_hash = load_script_hash()?;
+    let info_counts = (
+        count_info_cells(&index_state_type_hash, &info_type_code, Source::Input),
+        count_info_cells(&index_state_type_hash, &info_type_code, Source::Output),
+    );
+    if info_counts != expected_counts {
+        return Err(Error::TimeInfoCellsNotMigrated);
+    }
+    Ok(())
+}
+
+// The time index state cell with an owner can only be burnt by the owner, and all of its time
+// info cells must be burnt in the same transaction, so that no time info cell is left without
+// the index state cell. The cells without an owner keep the legacy destroy rules.
+fn check_index_state_cell_burnt() -> Result<(), Error> {
+    let index_state_type_args = load_index_state_type_args()?;
+    let (owner_lock_hash, info_type_code) = match (
+        index_state_type_args.owner_lock_hash,
+        index_state_type_args.info_type_code,
+    ) {
+        (Some(owner_lock_hash), Some(info_type_code)) => (owner_lock_hash, info_type_code),
+        _ => return Ok(()),
+    };
+    check_owner_authorized(&owner_lock_hash)?;
+
+    let index_state = check_index_state_cell_data(Source::GroupInput)?;
+    let index_state_type_hash = load_script_hash()?;
+    let info_inputs_count =
+        count_info_cells(&index_state_type_hash, &info_type_code, Source::Input);
+    if info_inputs_count != index_state.sum_of_time_info_cells as usize {
+        return Err(Error::TimeInfoCellsNotAllBurnt);
+    }
+    Ok(())
+}
+
+// The owner lock hash always comes with the info type code, see `IndexStateTypeArgs`
+fn load_index_state_type_args() -> Result<IndexStateTypeArgs, Error> {
+    let script = load_script()?;
+    let type_args: Bytes = script.args().unpack();
+    Ok(IndexStateTypeArgs::from_slice(&type_args)?)
+}
+
+fn check_owner_authorized(owner_lock_hash: &[u8; 32]) -> Result<(), Error> {
+    let is_owner = QueryIter::new(load_cell_lock_hash, Source::Input)
+        .any(|lock_hash| &lock_hash == owner_lock_hash);
+    if !is_owner {
+        return Err(Error::OwnerNotAuthorized);
+    }
+    Ok(())
+}
+
+// The time info cells are the cells whose type script has the info type code of the args and
+// whose type args start with the index state type hash, so the cells of any other type script
+// with the same args can't be counted. The legacy args without the info type code can only
+// match the type args.
+fn info_cell_positions<'a>(
+    index_state_type_hash: &'a [u8; 32],
+    info_type_code_opt: Option<&'a InfoTypeCode>,
+    source: Source,
+) -> impl Iterator<Item = usize> + 'a {
+    QueryIter::new(load_cell_type, source)
+        .enumerate()
+        .filter(move |(_, type_opt)| match type_opt {
+            Some(info_type) => {
+                let info_args: Bytes = info_type.args().unpack();
+                let is_code_same = match info_type_code_opt {
+                    Some(info_type_code) => info_type_code.is_code_of(
+                        info_type.code_hash().as_slice(),
+                        info_type.hash_type().as_slice()[0],
+                    ),
+                    None => true,
+                };
+                is_code_same
+                    && info_args.len() >= INDEX_STATE_TYPE_HASH_LEN
+                    && info_args[..INDEX_STATE_TYPE_HASH_LEN] == index_state_type_hash[..]
+            }
+            None => false,
+        })
+        .map(|(position, _)| position)
+}
+
+fn count_info_cells(
+    index_state_type_hash: &[u8; 32],
+    info_type_code: &InfoTypeCode,
+    source: Source,
+) -> usize {
+    info_cell_positions(index_state_type_hash, Some(info_type_code), source).count()
 }
@@ -1,4 +1,5 @@
 use ckb_std::error::SysError;
+use time_cell_types::rules::RuleError;
 
 #[repr(i8)]
 pub enum Error {
@@ -12,6 +13,21 @@ pub enum Error {
     TimeInfoAmountError,
     TimeIndexOutOfBound,
     TimeIndexIncreaseError,
+    TimeInfoAmountNotSame,
+    IndexStateInputsAmountError,
+    IndexStateOutputsAmountError,
+    InvalidIndexStateVersion,
+    IndexStateVersionDowngrade,
+    UpdaterNotAuthorized,
+    OwnerNotAuthorized,
+    TimeInfoCellsNotAllBurnt,
+    TimeIndexChangedInMigration,
+    TimeInfoCellsNotMigrated,
+    TimeInfoNotUpdated,
+    TimeInfoIndexNotSame,
+    UpdatersChanged,
+    IndexStateUpdatersEmpty,
+    TimeInfoCellsNotCreated,
 }
 
 impl From<SysError> for Error {
@@ -26,3 +42,52 @@ impl From<SysError> for Error {
         }
     }
 }
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        use time_cell_types::Error::*;
+        match err {
+            IndexStateDataLenError => Self::IndexStateDataLenError,
+            InvalidIndexStateVersion => Self::InvalidIndexStateVersion,
+            InvalidIndexStateTypeArgs => Self::InvalidArgument,
+            IndexStateUpdatersEmpty => Self::IndexStateUpdatersEmpty,
+            // The time info cell data which can't be decoded is rejected by the info type script,
+            // and the info type args and the consumer args are not decoded by the index state
+            // type script
+            TimeInfoDataLenError
+            | InvalidTimeInfoVersion
+            | InvalidTimeInfoKind
+            | InvalidTimeInfoEpoch
+            | InvalidInfoTypeArgs
+            | TimeInfoRingIncomplete
+            | TimeInfoKindNotSame
+            | InvalidTimeLockArgs
+            | InvalidTimeWindowArgs => Self::Encoding,
+        }
+    }
+}
+
+impl From<RuleError> for Error {
+    fn from(err: RuleError) -> Self {
+        use RuleError::*;
+        match err {
+            TimeInfoAmountError => Self::TimeInfoAmountError,
+            TimeIndexOutOfBound => Self::TimeIndexOutOfBound,
+            TimeIndexIncreaseError => Self::TimeIndexIncreaseError,
+            TimeIndexChangedInMigration => Self::TimeIndexChangedInMigration,
+            IndexStateVersionDowngrade => Self::IndexStateVersionDowngrade,
+            TimeInfoIndexNotSame => Self::TimeInfoIndexNotSame,
+            // The rules of the time info cells are checked by the info type script
+            TimeInfoVersionDowngrade
+            | TimeInfoDataLenError
+            | TimeInfoIndexOutOfBound
+            | TimeInfoSlotChanged
+            | TimeInfoSlotDuplicated
+            | TimeInfoSlotMissing
+            | TimeInfoKindNotSame
+            | TimeNotBigger
+            | TimeInfoStepTooSmall
+            | TimeInfoStepTooLarge => Self::Encoding,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use time_cell_types::blake2b_256;
+
+// The secp256k1 library of ckb-miscellaneous-scripts is built by `make deps`, and the info type
+// script loads it by its data hash, so a cell dep of another library can't verify the signatures
+const SECP256K1_LIB: &str = "../../deps/secp256k1_blake2b_sighash_all_dual";
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("manifest dir");
+    let lib_path = Path::new(&manifest_dir).join(SECP256K1_LIB);
+    println!("cargo:rerun-if-changed={}", lib_path.display());
+    let lib = fs::read(&lib_path).expect("secp256k1 library, run `make deps` first");
+
+    let out_dir = env::var("OUT_DIR").expect("out dir");
+    let code_hashes = format!(
+        "pub const CODE_HASH_SECP256K1_LIB: [u8; 32] = {:?};\n",
+        blake2b_256(&lib)
+    );
+    fs::write(Path::new(&out_dir).join("code_hashes.rs"), code_hashes).expect("code hashes");
+}
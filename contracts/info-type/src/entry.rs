@@ -1,156 +1,458 @@
 use crate::error::Error;
-use alloc::vec::Vec;
+use crate::secp256k1::LibSecp256k1;
+use alloc::{vec, vec::Vec};
 use ckb_std::{
     ckb_constants::Source,
-    ckb_types::{bytes::Bytes, packed::*, prelude::*},
-    high_level::{load_cell_data, load_cell_type, load_input_since, load_script, QueryIter},
+    ckb_types::{bytes::Bytes, packed::Script, prelude::*},
+    dynamic_loading::CKBDLContext,
+    high_level::{
+        load_cell, load_cell_data, load_cell_type, load_cell_type_hash, load_header,
+        load_input_out_point, load_input_since, load_script, load_script_hash, load_witness_args,
+        QueryIter,
+    },
 };
 use core::result::Result;
+use time_cell_types::{
+    blake160, info_update_message,
+    rules::{self, RuleError},
+    IndexStateData, IndexStateTypeArgs, InfoTypeArgs, TimeInfoData, TimeValue, MILLIS_PER_SECOND,
+    SIGNATURE_LEN,
+};
 
-const TIMESTAMP_DATA_LEN: usize = 5;
-const BLOCK_NUMBER_DATA_LEN: usize = 9;
-const INDEX_STATE_CELL_DATA_LEN: usize = 2;
+// The memory of the secp256k1 library which is loaded dynamically
+const SECP256K1_LIB_CONTEXT_SIZE: usize = 128 * 1024;
 
 pub fn main() -> Result<(), Error> {
-    if !check_type_script_exists_in_inputs()? {
-        // Create the time info cell and the input info type script doesn't exist
-        load_output_type_script(|output_type_script| {
-            let index_state_type_args = load_output_index_state_type_args()?;
-            let info_type_args: Bytes = output_type_script.args().unpack();
-            if info_type_args[..] != index_state_type_args[..] {
-                return Err(Error::InvalidArgument);
-            }
-            check_info_cell_data()
-        })
-    } else {
+    let info_type_args = load_info_type_args()?;
+    if let Some((input_index_state_data, output_index_state_data)) =
+        load_migrated_index_state_data(&info_type_args.index_state_type_hash)?
+    {
+        return check_info_cells_migrated(
+            &info_type_args.index_state_type_hash,
+            &input_index_state_data,
+            &output_index_state_data,
+        );
+    }
+
+    // Every transaction can only update one time info cell, except that all the time info cells
+    // are created with the time index state cell and burnt together with it
+    let group_inputs_count = QueryIter::new(load_cell, Source::GroupInput).count();
+    let group_outputs_count = QueryIter::new(load_cell, Source::GroupOutput).count();
+    if group_inputs_count > 1 && group_outputs_count > 0 {
+        return Err(Error::TimeInfoInputsAmountError);
+    }
+    if group_inputs_count > 0 && group_outputs_count > 1 {
+        return Err(Error::TimeInfoOutputsAmountError);
+    }
+
+    match (group_inputs_count, group_outputs_count) {
+        // Create all the time info cells and the input info type script doesn't exist
+        (0, _) => check_info_cells_genesis(&info_type_args),
         // Update the time info cell and the info type scripts of input and output exist
-        match check_cells_type_scripts_valid() {
-            Ok(_) => check_info_cells_data(),
-            Err(err) => Err(err),
-        }
+        (_, 1) => check_info_cells_data(&info_type_args),
+        // Burn all the time info cells with the time index state cell, and a single time info
+        // cell can't be destroyed, otherwise the ring is broken
+        _ => check_info_cells_burnt(&info_type_args, group_inputs_count),
     }
 }
 
-fn check_type_script_exists_in_inputs() -> Result<bool, Error> {
+// Time info type args: index state type script hash(32 bytes) | flags(u8, optional)
+//                       | min_step(u64, optional) | max_step(u64, optional)
+// The header dep proof flag requires the new time info to be equal to the time of the first
+// header dep, instead of the since of the time info input.
+// The step is the difference between the latest and output time info in the unit of the
+// versioned layout, and the max_step of zero means that there is no upper limit.
+// The legacy args are equal to the out point args of the time index state type script, and the
+// index state type hash is resolved from the time index state cell of the transaction.
+fn load_info_type_args() -> Result<InfoTypeArgs, Error> {
     let script = load_script()?;
-    let type_script_exists_in_inputs = QueryIter::new(load_cell_type, Source::Input).any(
-        |type_script_opt| match type_script_opt {
-            Some(type_script) => {
-                type_script.code_hash().raw_data()[..] == script.code_hash().raw_data()[..]
+    let type_args: Bytes = script.args().unpack();
+    if InfoTypeArgs::is_legacy(&type_args) {
+        return Ok(InfoTypeArgs::new(find_legacy_index_state_type_hash(
+            &script,
+        )?));
+    }
+    Ok(InfoTypeArgs::from_slice(&type_args)?)
+}
+
+// The time index state cell of the legacy args is the cell of the inputs or outputs whose type
+// script has another code than this script and the same args, and all of them must have the same
+// type script
+fn find_legacy_index_state_type_hash(script: &Script) -> Result<[u8; 32], Error> {
+    let mut index_state_type_hash = None;
+    for source in [Source::Input, Source::Output].iter() {
+        for (position, type_opt) in QueryIter::new(load_cell_type, *source).enumerate() {
+            let is_index_state = match type_opt {
+                Some(cell_type) => {
+                    cell_type.args().as_slice() == script.args().as_slice()
+                        && (cell_type.code_hash().as_slice() != script.code_hash().as_slice()
+                            || cell_type.hash_type().as_slice() != script.hash_type().as_slice())
+                }
+                None => false,
+            };
+            if !is_index_state {
+                continue;
             }
+            let type_hash = load_cell_type_hash(position, *source)?;
+            match index_state_type_hash {
+                Some(index_state_type_hash) if type_hash != Some(index_state_type_hash) => {
+                    return Err(Error::IndexStateTypeDuplicated);
+                }
+                _ => index_state_type_hash = type_hash,
+            }
+        }
+    }
+    index_state_type_hash.ok_or(Error::IndexStateTypeNotExist)
+}
+
+// The index state cell must be the only output whose type script hash equals the info type args
+fn load_output_index_state_data(index_state_type_hash: &[u8; 32]) -> Result<IndexStateData, Error> {
+    let index_state_position = load_index_state_position(index_state_type_hash, Source::Output)?;
+
+    // Time index state cell data: index(u8) | sum_of_time_info_cells(u8)
+    let index_state_data = load_cell_data(index_state_position, Source::Output)?;
+    Ok(IndexStateData::from_slice(&index_state_data)?)
+}
+
+fn load_index_state_position(
+    index_state_type_hash: &[u8; 32],
+    source: Source,
+) -> Result<usize, Error> {
+    match find_index_state_position(index_state_type_hash, source)? {
+        Some(position) => Ok(position),
+        None => Err(Error::IndexStateTypeNotExist),
+    }
+}
+
+fn find_index_state_position(
+    index_state_type_hash: &[u8; 32],
+    source: Source,
+) -> Result<Option<usize>, Error> {
+    let mut index_state_positions = QueryIter::new(load_cell_type_hash, source)
+        .enumerate()
+        .filter(|(_, type_hash_opt)| match type_hash_opt {
+            Some(type_hash) => type_hash == index_state_type_hash,
             None => false,
-        },
-    );
-    Ok(type_script_exists_in_inputs)
+        })
+        .map(|(index, _)| index);
+    let index_state_position = index_state_positions.next();
+    if index_state_positions.next().is_some() {
+        return Err(Error::IndexStateTypeDuplicated);
+    }
+    Ok(index_state_position)
 }
 
-fn load_output_type_script<F>(closure: F) -> Result<(), Error>
-where
-    F: Fn(Script) -> Result<(), Error>,
-{
-    match load_cell_type(0, Source::GroupOutput) {
-        Ok(Some(output_type_script)) => closure(output_type_script),
-        Ok(None) => Err(Error::TimeInfoTypeNotExist),
-        Err(_) => Err(Error::TimeInfoTypeNotExist),
+// The ring of the time info cells is migrated when the sum_of_time_info_cells of the time index
+// state cell is changed, and the owner of the time index state cell is checked by the index
+// state type script
+fn load_migrated_index_state_data(
+    index_state_type_hash: &[u8; 32],
+) -> Result<Option<(IndexStateData, IndexStateData)>, Error> {
+    let input_position = find_index_state_position(index_state_type_hash, Source::Input)?;
+    let output_position = find_index_state_position(index_state_type_hash, Source::Output)?;
+    let (input_position, output_position) = match (input_position, output_position) {
+        (Some(input_position), Some(output_position)) => (input_position, output_position),
+        _ => return Ok(None),
+    };
+    let input_data = load_cell_data(input_position, Source::Input)?;
+    let output_data = load_cell_data(output_position, Source::Output)?;
+    let input_index_state_data = IndexStateData::from_slice(&input_data)?;
+    let output_index_state_data = IndexStateData::from_slice(&output_data)?;
+    if input_index_state_data.sum_of_time_info_cells
+        == output_index_state_data.sum_of_time_info_cells
+    {
+        return Ok(None);
     }
+    Ok(Some((input_index_state_data, output_index_state_data)))
 }
 
-fn load_output_index_state_type_args() -> Result<Bytes, Error> {
-    match load_cell_type(0, Source::Output) {
-        Ok(Some(output_type_script)) => {
-            let type_args: Bytes = output_type_script.args().unpack();
-            Ok(type_args)
+// When the ring grows, the time info cells of the new slots are created, and when the ring
+// shrinks, the time info cells of the removed slots are burnt. The other time info cells can't
+// be touched by the migration.
+fn check_info_cells_migrated(
+    index_state_type_hash: &[u8; 32],
+    input_index_state_data: &IndexStateData,
+    output_index_state_data: &IndexStateData,
+) -> Result<(), Error> {
+    let input_sum = input_index_state_data.sum_of_time_info_cells;
+    let output_sum = output_index_state_data.sum_of_time_info_cells;
+    if output_sum > input_sum {
+        if QueryIter::new(load_cell, Source::GroupInput).count() > 0 {
+            return Err(Error::TimeInfoInputsAmountError);
+        }
+        check_info_slots(index_state_type_hash, Source::Output, input_sum, output_sum)
+    } else {
+        if QueryIter::new(load_cell, Source::GroupOutput).count() > 0 {
+            return Err(Error::TimeInfoOutputsAmountError);
         }
-        Ok(None) => Err(Error::IndexStateTypeNotExist),
-        Err(_) => Err(Error::IndexStateTypeNotExist),
+        check_info_slots(index_state_type_hash, Source::Input, output_sum, input_sum)
     }
 }
 
-// Time info cell data: index(u8) | timestamp(u32) or block number(u64)
-fn check_info_cell_data() -> Result<(), Error> {
-    match load_cell_data(0, Source::GroupOutput) {
-        Ok(info_data) => {
-            if is_info_data_len_invalid(&info_data) {
-                return Err(Error::TimeInfoDataLenError);
+// Every slot from start to end(exclusive) must be taken by exactly one time info cell of the
+// source. The time info cells are all the cells of the source whose type script has the code of
+// this script and the args starting with the index state type hash, not only the cells of the
+// script group, and they must share the same args, otherwise the cells of other info type args
+// could take the slots of the ring of the same time index state cell.
+fn check_info_slots(
+    index_state_type_hash: &[u8; 32],
+    source: Source,
+    start: u8,
+    end: u8,
+) -> Result<(), Error> {
+    let script = load_script()?;
+    let mut indexes = Vec::new();
+    for (position, type_opt) in QueryIter::new(load_cell_type, source).enumerate() {
+        let info_type = match type_opt {
+            Some(info_type) if is_info_type_of(&info_type, &script, index_state_type_hash) => {
+                info_type
             }
-            Ok(())
+            _ => continue,
+        };
+        if info_type.as_slice() != script.as_slice() {
+            return Err(Error::TimeInfoTypeArgsNotSame);
         }
-        Err(_) => Err(Error::TimeInfoTypeNotExist),
+        let info_data = load_cell_data(position, source)?;
+        indexes.push(TimeInfoData::from_slice(&info_data)?.index());
     }
+    Ok(rules::check_info_slots(indexes, start, end)?)
 }
 
-fn check_cells_type_scripts_valid() -> Result<(), Error> {
-    load_output_type_script(|_| match load_cell_type(0, Source::GroupInput) {
-        Ok(Some(_)) => Ok(()),
-        Ok(None) => Err(Error::TimeInfoTypeNotExist),
-        Err(_) => Err(Error::TimeInfoTypeNotExist),
-    })
+// The cells with the legacy args of this script are the time info cells of the same time index
+// state cell too
+fn is_info_type_of(cell_type: &Script, script: &Script, index_state_type_hash: &[u8; 32]) -> bool {
+    let cell_type_args: Bytes = cell_type.args().unpack();
+    cell_type.code_hash().as_slice() == script.code_hash().as_slice()
+        && cell_type.hash_type().as_slice() == script.hash_type().as_slice()
+        && (cell_type_args.starts_with(index_state_type_hash)
+            || cell_type.args().as_slice() == script.args().as_slice())
 }
 
-fn check_info_cells_data() -> Result<(), Error> {
-    // Note: Assuming that the first output must have index state type
-    let index_state_data = load_cell_data(0, Source::Output)?;
-    if index_state_data.len() != INDEX_STATE_CELL_DATA_LEN {
-        return Err(Error::IndexStateDataLenError);
+// The time info cells can be burnt together only when the time index state cell with an owner is
+// burnt in the same transaction, and none of them can be left behind. The owner of the time index
+// state cell is checked by the index state type script, and the time info cells of the time
+// index state cell without an owner can't be burnt.
+fn check_info_cells_burnt(
+    info_type_args: &InfoTypeArgs,
+    group_inputs_count: usize,
+) -> Result<(), Error> {
+    let index_state_type_hash = &info_type_args.index_state_type_hash;
+    let index_state_position = load_index_state_position(index_state_type_hash, Source::Input)?;
+    let is_index_state_kept = QueryIter::new(load_cell_type_hash, Source::Output)
+        .any(|type_hash_opt| type_hash_opt.as_ref() == Some(index_state_type_hash));
+    if is_index_state_kept {
+        return Err(Error::IndexStateNotBurnt);
     }
 
-    let input_info_data = load_cell_data(0, Source::GroupInput)?;
-    let output_info_data = load_cell_data(0, Source::GroupOutput)?;
+    let index_state_type = load_cell_type(index_state_position, Source::Input)?
+        .ok_or(Error::IndexStateTypeNotExist)?;
+    let index_state_type_args: Bytes = index_state_type.args().unpack();
+    if IndexStateTypeArgs::from_slice(&index_state_type_args)?
+        .owner_lock_hash
+        .is_none()
+    {
+        return Err(Error::IndexStateOwnerNotExist);
+    }
 
-    if is_info_data_len_invalid(&output_info_data) {
-        return Err(Error::TimeInfoDataLenError);
+    let index_state_data = load_cell_data(index_state_position, Source::Input)?;
+    let index_state_data = IndexStateData::from_slice(&index_state_data)?;
+    if group_inputs_count != index_state_data.sum_of_time_info_cells as usize {
+        return Err(Error::TimeInfoCellsNotAllBurnt);
     }
+    Ok(())
+}
 
-    if output_info_data.len() != input_info_data.len() {
-        return Err(Error::TimeInfoDataLenError);
+// The genesis transaction creates the time index state cell and exactly sum_of_time_info_cells
+// time info cells with the indexes from 0 to sum_of_time_info_cells - 1, and no time info cell
+// can be created after that except the ring-size migration.
+// Time info cell data has two layouts:
+// legacy: index(u8) | timestamp(u32, seconds) or block number(u64)
+// versioned: version(u8) | index(u8) | kind(u8) | timestamp(u64, milliseconds), block number(u64)
+//            or epoch(u64)
+// The legacy layouts are told apart by their lengths, otherwise the first byte is the version
+fn check_info_cells_genesis(info_type_args: &InfoTypeArgs) -> Result<(), Error> {
+    let index_state_type_hash = &info_type_args.index_state_type_hash;
+    if find_index_state_position(index_state_type_hash, Source::Input)?.is_some() {
+        return Err(Error::TimeInfoCreatedAfterGenesis);
     }
+    let index_state_data = load_output_index_state_data(index_state_type_hash)?;
+    check_info_type_code_committed(index_state_type_hash)?;
+    check_info_slots(
+        index_state_type_hash,
+        Source::Output,
+        0,
+        index_state_data.sum_of_time_info_cells,
+    )
+}
 
-    if output_info_data[0] != index_state_data[0] {
-        return Err(Error::TimeInfoIndexNotSame);
+// The info type code committed by the time index state type args must be the code of this
+// script, otherwise the index state type script would count the cells of another type script
+// as the time info cells
+fn check_info_type_code_committed(index_state_type_hash: &[u8; 32]) -> Result<(), Error> {
+    let index_state_position = load_index_state_position(index_state_type_hash, Source::Output)?;
+    let index_state_type = load_cell_type(index_state_position, Source::Output)?
+        .ok_or(Error::IndexStateTypeNotExist)?;
+    let index_state_type_args: Bytes = index_state_type.args().unpack();
+    if let Some(info_type_code) =
+        IndexStateTypeArgs::from_slice(&index_state_type_args)?.info_type_code
+    {
+        let script = load_script()?;
+        if !info_type_code.is_code_of(
+            script.code_hash().as_slice(),
+            script.hash_type().as_slice()[0],
+        ) {
+            return Err(Error::InfoTypeCodeNotSame);
+        }
     }
+    Ok(())
+}
 
-    let since = load_input_since(0, Source::GroupInput)?;
+fn check_info_cells_data(info_type_args: &InfoTypeArgs) -> Result<(), Error> {
+    let index_state_data = load_output_index_state_data(&info_type_args.index_state_type_hash)?;
 
-    if output_info_data.len() == TIMESTAMP_DATA_LEN {
-        let input_timestamp = timestamp_from_info_data(&input_info_data);
-        let output_timestamp = timestamp_from_info_data(&output_info_data);
+    let input_info_data = load_cell_data(0, Source::GroupInput)?;
+    let output_info_data = load_cell_data(0, Source::GroupOutput)?;
 
-        if input_timestamp >= output_timestamp {
-            return Err(Error::OutputTimestampNotBigger);
-        }
+    let output_info = TimeInfoData::from_slice(&output_info_data)?;
+    let input_info = TimeInfoData::from_slice(&input_info_data)?;
 
-        let since_timestamp_base: u64 = 1 << 62;
-        if since_timestamp_base + output_timestamp as u64 != since {
-            return Err(Error::InvalidTimeInfoSince);
-        }
-    } else if output_info_data.len() == BLOCK_NUMBER_DATA_LEN {
-        let input_block_number = block_number_from_info_data(&input_info_data);
-        let output_block_number = block_number_from_info_data(&output_info_data);
+    // Every time info cell owns a fixed slot of the ring, so the time info cell of the slot
+    // pointed by the new index is the one to be overwritten, and the ring always holds the
+    // latest sum_of_time_info_cells values. The rules of the decoded cells are shared with the
+    // SDK and the CLI, see `time_cell_types::rules`.
+    rules::check_info_updated(&input_info, &output_info, &index_state_data)?;
 
-        if input_block_number >= output_block_number {
-            return Err(Error::OutputBlockNumberNotBigger);
-        }
+    // The new time is compared with the latest time of the ring rather than the overwritten
+    // one, otherwise the oracle could move backwards
+    let latest_info = load_latest_info(&info_type_args.index_state_type_hash, &input_info)?;
+    let output_value = output_info.value();
+    rules::check_info_time(&output_info, &latest_info, info_type_args).map_err(
+        |err| match err {
+            RuleError::TimeNotBigger => match output_value {
+                TimeValue::Timestamp(_) | TimeValue::TimestampMillis(_) => {
+                    Error::OutputTimestampNotBigger
+                }
+                TimeValue::BlockNumber(_) => Error::OutputBlockNumberNotBigger,
+                TimeValue::Epoch(_) => Error::OutputEpochNotBigger,
+            },
+            err => err.into(),
+        },
+    )?;
 
-        if output_block_number != since {
+    if info_type_args.has_header_dep_proof() {
+        check_info_with_header_dep(&output_value)?;
+    } else {
+        let since = load_input_since(0, Source::GroupInput)?;
+        if since != output_value.since() {
             return Err(Error::InvalidTimeInfoSince);
         }
     }
 
+    if info_type_args.has_multi_sig() {
+        check_operator_signatures(info_type_args, &output_info_data)?;
+    }
+
     Ok(())
 }
 
-fn timestamp_from_info_data(info_data: &Vec<u8>) -> u32 {
-    let mut timestamp_buf = [0u8; TIMESTAMP_DATA_LEN - 1];
-    timestamp_buf.copy_from_slice(&info_data[1..]);
-    u32::from_be_bytes(timestamp_buf)
+// The latest time info is the time info cell of the current index of the input time index state
+// cell. It's the input time info cell itself only if the ring has one slot, otherwise it must be
+// a cell dep with the same info type script.
+fn load_latest_info(
+    index_state_type_hash: &[u8; 32],
+    input_info: &TimeInfoData,
+) -> Result<TimeInfoData, Error> {
+    let index_state_position = load_index_state_position(index_state_type_hash, Source::Input)?;
+    let index_state_data = load_cell_data(index_state_position, Source::Input)?;
+    let latest_index = IndexStateData::from_slice(&index_state_data)?.index;
+    if input_info.index() == latest_index {
+        return Ok(*input_info);
+    }
+
+    let info_type_hash = load_script_hash()?;
+    for (position, type_hash_opt) in
+        QueryIter::new(load_cell_type_hash, Source::CellDep).enumerate()
+    {
+        if type_hash_opt != Some(info_type_hash) {
+            continue;
+        }
+        let info_data = load_cell_data(position, Source::CellDep)?;
+        let info = TimeInfoData::from_slice(&info_data)?;
+        if info.index() == latest_index {
+            return Ok(info);
+        }
+    }
+    Err(Error::LatestTimeInfoNotExist)
 }
 
-fn block_number_from_info_data(info_data: &Vec<u8>) -> u64 {
-    let mut block_number_buf = [0u8; BLOCK_NUMBER_DATA_LEN - 1];
-    block_number_buf.copy_from_slice(&info_data[1..]);
-    u64::from_be_bytes(block_number_buf)
+// The input_type of the group witness carries the recoverable signatures of the operators:
+// signature(65 bytes) * M, and every signature must be from a different operator of the info
+// type args. The signed message is blake2b(out point of the time info input | output time info
+// cell data), so a signature can't be replayed for another update.
+// The public keys are recovered by the secp256k1 library of CKB, which must be a cell dep.
+fn check_operator_signatures(
+    info_type_args: &InfoTypeArgs,
+    output_info_data: &[u8],
+) -> Result<(), Error> {
+    let signatures: Bytes = match load_witness_args(0, Source::GroupInput) {
+        Ok(witness_args) => match witness_args.input_type().to_opt() {
+            Some(signatures) => signatures.unpack(),
+            None => return Err(Error::SignaturesNotExist),
+        },
+        Err(_) => return Err(Error::SignaturesNotExist),
+    };
+    if signatures.is_empty() {
+        return Err(Error::SignaturesNotExist);
+    }
+    if signatures.len() % SIGNATURE_LEN != 0 {
+        return Err(Error::InvalidSignature);
+    }
+
+    let input_out_point = load_input_out_point(0, Source::GroupInput)?;
+    let message = info_update_message(input_out_point.as_slice(), output_info_data);
+
+    let mut context = unsafe { CKBDLContext::<[u8; SECP256K1_LIB_CONTEXT_SIZE]>::new() };
+    let lib = LibSecp256k1::load(&mut context)?;
+    let mut is_signed = vec![false; info_type_args.operators.len()];
+    for signature in signatures.chunks(SIGNATURE_LEN) {
+        let pubkey_hash = blake160(&lib.recover_pubkey(signature, &message)?);
+        match info_type_args
+            .operators
+            .iter()
+            .position(|operator| operator == &pubkey_hash)
+        {
+            Some(position) if !is_signed[position] => is_signed[position] = true,
+            // The signer isn't an operator or has signed already
+            _ => return Err(Error::InvalidSignature),
+        }
+    }
+
+    let signed_count = is_signed.iter().filter(|signed| **signed).count();
+    if signed_count < info_type_args.threshold as usize {
+        return Err(Error::SignaturesNotEnough);
+    }
+    Ok(())
 }
 
-fn is_info_data_len_invalid(info_data: &Vec<u8>) -> bool {
-    info_data.len() != TIMESTAMP_DATA_LEN && info_data.len() != BLOCK_NUMBER_DATA_LEN
+// The time info must be equal to the time of the first header dep, which proves that the time
+// info comes from a real block rather than a lower bound
+fn check_info_with_header_dep(value: &TimeValue) -> Result<(), Error> {
+    let raw_header = match load_header(0, Source::HeaderDep) {
+        Ok(header) => header.raw(),
+        Err(_) => return Err(Error::HeaderDepNotExist),
+    };
+    let header_timestamp: u64 = raw_header.timestamp().unpack();
+    let header_block_number: u64 = raw_header.number().unpack();
+    let header_epoch: u64 = raw_header.epoch().unpack();
+    let is_time_same = match *value {
+        // The timestamp of header is in milliseconds
+        TimeValue::Timestamp(timestamp) => timestamp as u64 == header_timestamp / MILLIS_PER_SECOND,
+        TimeValue::TimestampMillis(timestamp) => timestamp == header_timestamp,
+        TimeValue::BlockNumber(block_number) => block_number == header_block_number,
+        TimeValue::Epoch(epoch) => epoch == header_epoch,
+    };
+    if !is_time_same {
+        return Err(Error::TimeInfoNotSameAsHeader);
+    }
+    Ok(())
 }
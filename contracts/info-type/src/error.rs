@@ -1,4 +1,5 @@
 use ckb_std::error::SysError;
+use time_cell_types::rules::RuleError;
 
 #[repr(i8)]
 pub enum Error {
@@ -9,12 +10,42 @@ pub enum Error {
     InvalidArgument = 5,
     TimeInfoDataLenError,
     IndexStateDataLenError,
-    TimeInfoTypeNotExist,
-    TimeInfoIndexNotSame,
+    // The error code 8 is reserved, which was returned when the info type script didn't exist
+    TimeInfoIndexNotSame = 9,
     OutputTimestampNotBigger = 10,
     OutputBlockNumberNotBigger,
     InvalidTimeInfoSince,
     IndexStateTypeNotExist,
+    TimeInfoIndexOutOfBound,
+    IndexStateTypeDuplicated,
+    TimeInfoInputsAmountError,
+    TimeInfoOutputsAmountError,
+    InvalidTimeInfoVersion,
+    InvalidTimeInfoKind,
+    TimeInfoKindNotSame,
+    OutputEpochNotBigger,
+    InvalidTimeInfoEpoch,
+    HeaderDepNotExist,
+    TimeInfoNotSameAsHeader,
+    TimeInfoStepTooSmall,
+    TimeInfoStepTooLarge,
+    TimeInfoVersionDowngrade,
+    InvalidIndexStateVersion,
+    SignaturesNotExist,
+    InvalidSignature,
+    SignaturesNotEnough,
+    IndexStateNotBurnt,
+    TimeInfoCellsNotAllBurnt,
+    TimeInfoSlotDuplicated,
+    TimeInfoSlotMissing,
+    TimeInfoSlotChanged,
+    TimeInfoCreatedAfterGenesis,
+    LatestTimeInfoNotExist,
+    IndexStateOwnerNotExist,
+    InfoTypeCodeNotSame,
+    TimeInfoTypeArgsNotSame,
+    // The secp256k1 library isn't a cell dep, or it can't be loaded
+    Secp256k1LibNotExist,
 }
 
 impl From<SysError> for Error {
@@ -29,3 +60,51 @@ impl From<SysError> for Error {
         }
     }
 }
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        use time_cell_types::Error::*;
+        match err {
+            IndexStateDataLenError | IndexStateUpdatersEmpty => Self::IndexStateDataLenError,
+            TimeInfoDataLenError => Self::TimeInfoDataLenError,
+            InvalidTimeInfoVersion => Self::InvalidTimeInfoVersion,
+            InvalidTimeInfoKind => Self::InvalidTimeInfoKind,
+            InvalidTimeInfoEpoch => Self::InvalidTimeInfoEpoch,
+            InvalidInfoTypeArgs => Self::InvalidArgument,
+            InvalidIndexStateVersion => Self::InvalidIndexStateVersion,
+            InvalidIndexStateTypeArgs => Self::InvalidArgument,
+            // The ring time and the consumer args are not used by the info type script
+            TimeInfoRingIncomplete
+            | TimeInfoKindNotSame
+            | InvalidTimeLockArgs
+            | InvalidTimeWindowArgs => Self::Encoding,
+        }
+    }
+}
+
+impl From<RuleError> for Error {
+    fn from(err: RuleError) -> Self {
+        use RuleError::*;
+        match err {
+            TimeInfoVersionDowngrade => Self::TimeInfoVersionDowngrade,
+            TimeInfoDataLenError => Self::TimeInfoDataLenError,
+            TimeInfoIndexOutOfBound => Self::TimeInfoIndexOutOfBound,
+            TimeInfoIndexNotSame => Self::TimeInfoIndexNotSame,
+            TimeInfoSlotChanged => Self::TimeInfoSlotChanged,
+            TimeInfoSlotDuplicated => Self::TimeInfoSlotDuplicated,
+            TimeInfoSlotMissing => Self::TimeInfoSlotMissing,
+            TimeInfoKindNotSame => Self::TimeInfoKindNotSame,
+            TimeInfoStepTooSmall => Self::TimeInfoStepTooSmall,
+            TimeInfoStepTooLarge => Self::TimeInfoStepTooLarge,
+            // The error of the time which isn't bigger depends on the kind of the time, so it's
+            // mapped by the caller of check_info_time
+            TimeNotBigger => Self::OutputTimestampNotBigger,
+            // The time index state cell is checked by the index state type script
+            TimeInfoAmountError
+            | TimeIndexOutOfBound
+            | TimeIndexIncreaseError
+            | TimeIndexChangedInMigration
+            | IndexStateVersionDowngrade => Self::Encoding,
+        }
+    }
+}
@@ -6,11 +6,13 @@
 
 mod entry;
 mod error;
+mod secp256k1;
 
 use ckb_std::default_alloc;
 
 ckb_std::entry!(program_entry);
-default_alloc!();
+// The heap holds the prefilled data(1 MB) of the secp256k1 library
+default_alloc!(4 * 1024, 1280 * 1024, 64);
 
 fn program_entry() -> i8 {
     match entry::main() {
@@ -0,0 +1,84 @@
+use crate::error::Error;
+use alloc::{
+    alloc::{alloc, Layout},
+    boxed::Box,
+};
+use ckb_std::dynamic_loading::{CKBDLContext, Symbol};
+
+include!(concat!(env!("OUT_DIR"), "/code_hashes.rs"));
+
+// The functions of secp256k1_blake2b_sighash_all_dual of ckb-miscellaneous-scripts
+type LoadPrefilledData = unsafe extern "C" fn(data: *mut u8, len: *mut u64) -> i32;
+type ValidateSignature = unsafe extern "C" fn(
+    prefilled_data: *const u8,
+    signature: *const u8,
+    signature_len: u64,
+    message: *const u8,
+    message_len: u64,
+    pubkey: *mut u8,
+    pubkey_len: *mut u64,
+) -> i32;
+
+const SECP256K1_DATA_SIZE: usize = 1_048_576;
+const COMPRESSED_PUBKEY_LEN: usize = 33;
+
+/// The secp256k1 library of CKB in C, which is loaded from the cell dep of the library by its
+/// data hash. It recovers a public key with much fewer cycles than the pure Rust implementation.
+pub struct LibSecp256k1 {
+    prefilled_data: Box<[u8; SECP256K1_DATA_SIZE]>,
+    validate_signature: ValidateSignature,
+}
+
+impl LibSecp256k1 {
+    /// The library must not be used after the context is dropped
+    pub fn load<T>(context: &mut CKBDLContext<T>) -> Result<Self, Error> {
+        let lib = context
+            .load(&CODE_HASH_SECP256K1_LIB)
+            .map_err(|_| Error::Secp256k1LibNotExist)?;
+        let load_prefilled_data: Symbol<LoadPrefilledData> =
+            unsafe { lib.get(b"load_prefilled_data") }.ok_or(Error::Secp256k1LibNotExist)?;
+        let validate_signature: Symbol<ValidateSignature> =
+            unsafe { lib.get(b"validate_signature") }.ok_or(Error::Secp256k1LibNotExist)?;
+
+        // The prefilled data is too large to be built on the stack before it's boxed
+        let mut prefilled_data = unsafe {
+            let data = alloc(Layout::new::<[u8; SECP256K1_DATA_SIZE]>());
+            Box::from_raw(data as *mut [u8; SECP256K1_DATA_SIZE])
+        };
+        let mut len = SECP256K1_DATA_SIZE as u64;
+        let load_prefilled_data = *load_prefilled_data;
+        if unsafe { load_prefilled_data(prefilled_data.as_mut_ptr(), &mut len) } != 0 {
+            return Err(Error::Secp256k1LibNotExist);
+        }
+        Ok(LibSecp256k1 {
+            prefilled_data,
+            validate_signature: *validate_signature,
+        })
+    }
+
+    /// Recover the compressed public key from the recoverable signature(65 bytes) of the message
+    pub fn recover_pubkey(
+        &self,
+        signature: &[u8],
+        message: &[u8; 32],
+    ) -> Result<[u8; COMPRESSED_PUBKEY_LEN], Error> {
+        let mut pubkey = [0u8; COMPRESSED_PUBKEY_LEN];
+        let mut pubkey_len = COMPRESSED_PUBKEY_LEN as u64;
+        let validate_signature = self.validate_signature;
+        let error_code = unsafe {
+            validate_signature(
+                self.prefilled_data.as_ptr(),
+                signature.as_ptr(),
+                signature.len() as u64,
+                message.as_ptr(),
+                message.len() as u64,
+                pubkey.as_mut_ptr(),
+                &mut pubkey_len,
+            )
+        };
+        if error_code != 0 || pubkey_len != COMPRESSED_PUBKEY_LEN as u64 {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(pubkey)
+    }
+}
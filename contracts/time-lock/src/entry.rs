@@ -0,0 +1,43 @@
+use crate::error::Error;
+use ckb_std::{
+    ckb_constants::Source,
+    ckb_types::{bytes::Bytes, prelude::*},
+    high_level::{load_cell_lock_hash, load_script, QueryIter},
+};
+use core::result::Result;
+use time_cell_types::TimeLockArgs;
+use time_oracle_reader::{load_latest_time, load_ring_time};
+
+// Time lock args: info type script hash(32 bytes) | kind(u8) | threshold(u64)
+//                 | owner lock hash(32 bytes) | flags(u8, optional)
+// The owner lock hash is mandatory, otherwise anyone could spend the cell once the threshold is
+// reached, because the time of the oracle is the only condition of the lock
+pub fn main() -> Result<(), Error> {
+    let script = load_script()?;
+    let args: Bytes = script.args().unpack();
+    let time_lock_args = TimeLockArgs::from_slice(&args)?;
+
+    // The time of the oracle is compared in the unit of the versioned layout, so the timestamp
+    // threshold is in milliseconds. With the ring median flag, all the time info cells of the
+    // ring must be the cell deps, and a single bad update can't unlock the cell early.
+    let threshold = time_lock_args.threshold_time();
+    let oracle_time = if time_lock_args.has_ring_median() {
+        load_ring_time(&time_lock_args.info_type_hash)?.median
+    } else {
+        load_latest_time(&time_lock_args.info_type_hash)?.to_versioned()
+    };
+    if !oracle_time.is_same_kind(&threshold) {
+        return Err(Error::TimeKindNotSame);
+    }
+    if threshold.is_bigger_than(&oracle_time) {
+        return Err(Error::TimeNotReached);
+    }
+
+    // The cell can only be unlocked by the owner
+    let is_owner = QueryIter::new(load_cell_lock_hash, Source::Input)
+        .any(|lock_hash| lock_hash == time_lock_args.owner_lock_hash);
+    if !is_owner {
+        return Err(Error::OwnerNotAuthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use ckb_std::error::SysError;
+
+#[repr(i8)]
+pub enum Error {
+    IndexOutOfBound = 1,
+    ItemMissing,
+    LengthNotEnough,
+    Encoding,
+    InvalidArgument = 5,
+    TimeInfoNotFound,
+    IndexStateNotFound,
+    IndexStateDuplicated,
+    LatestTimeInfoNotFound,
+    InvalidInfoTypeArgs,
+    InvalidIndexStateData,
+    InvalidTimeInfoData,
+    TimeKindNotSame,
+    TimeNotReached,
+    OwnerNotAuthorized,
+    LatestTimeInfoDuplicated,
+    TimeInfoRingIncomplete,
+}
+
+impl From<SysError> for Error {
+    fn from(err: SysError) -> Self {
+        use SysError::*;
+        match err {
+            IndexOutOfBound => Self::IndexOutOfBound,
+            ItemMissing => Self::ItemMissing,
+            LengthNotEnough(_) => Self::LengthNotEnough,
+            Encoding => Self::Encoding,
+            Unknown(err_code) => panic!("unexpected sys error {}", err_code),
+        }
+    }
+}
+
+impl From<time_cell_types::Error> for Error {
+    fn from(_err: time_cell_types::Error) -> Self {
+        Self::InvalidArgument
+    }
+}
+
+impl From<time_oracle_reader::Error> for Error {
+    fn from(err: time_oracle_reader::Error) -> Self {
+        use time_oracle_reader::Error::*;
+        match err {
+            IndexOutOfBound => Self::IndexOutOfBound,
+            ItemMissing => Self::ItemMissing,
+            LengthNotEnough => Self::LengthNotEnough,
+            Encoding => Self::Encoding,
+            TimeInfoNotFound => Self::TimeInfoNotFound,
+            IndexStateNotFound => Self::IndexStateNotFound,
+            IndexStateDuplicated => Self::IndexStateDuplicated,
+            LatestTimeInfoNotFound => Self::LatestTimeInfoNotFound,
+            InvalidInfoTypeArgs => Self::InvalidInfoTypeArgs,
+            // The info type code is committed by the time index state type args
+            InvalidIndexStateData | InfoTypeCodeNotCommitted => Self::InvalidIndexStateData,
+            LatestTimeInfoDuplicated => Self::LatestTimeInfoDuplicated,
+            InvalidTimeInfoData | TimeInfoKindNotSame => Self::InvalidTimeInfoData,
+            TimeInfoRingIncomplete => Self::TimeInfoRingIncomplete,
+        }
+    }
+}
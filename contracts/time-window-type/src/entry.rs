@@ -0,0 +1,40 @@
+use crate::error::Error;
+use ckb_std::{
+    ckb_constants::Source,
+    ckb_types::{bytes::Bytes, prelude::*},
+    high_level::{load_cell, load_script, QueryIter},
+};
+use core::result::Result;
+use time_cell_types::TimeWindowArgs;
+use time_oracle_reader::load_latest_time;
+
+// Time window type args: info type script hash(32 bytes) | kind(u8) | start(u64) | end(u64)
+pub fn main() -> Result<(), Error> {
+    let script = load_script()?;
+    let args: Bytes = script.args().unpack();
+    let time_window_args = TimeWindowArgs::from_slice(&args)?;
+    let start = time_window_args.start_time();
+    let end = time_window_args.end_time();
+
+    // The cells can only be created in the window, so they can always be destroyed, otherwise
+    // their capacity would be locked forever after the end
+    if QueryIter::new(load_cell, Source::GroupOutput).count() == 0 {
+        return Ok(());
+    }
+
+    // The cells guarded by the type script can be created or transferred only when the latest
+    // time of the oracle is in the window [start, end]. The latest time is never fresher than the
+    // last update of the oracle, and nothing on chain bounds the time of the transaction from
+    // above, so a transaction can still pass after the end if the oracle stops being updated.
+    let latest_time = load_latest_time(&time_window_args.info_type_hash)?.to_versioned();
+    if !latest_time.is_same_kind(&start) {
+        return Err(Error::TimeKindNotSame);
+    }
+    if start.is_bigger_than(&latest_time) {
+        return Err(Error::TimeWindowNotStarted);
+    }
+    if latest_time.is_bigger_than(&end) {
+        return Err(Error::TimeWindowClosed);
+    }
+    Ok(())
+}
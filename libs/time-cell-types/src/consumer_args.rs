@@ -0,0 +1,142 @@
+use crate::error::Error;
+use crate::{
+    u64_from_be_slice, TimeKind, TimeValue, TIME_LOCK_ARGS_LEN, TIME_WINDOW_TYPE_ARGS_LEN,
+};
+use alloc::vec::Vec;
+
+/// Time lock args: info type script hash(32 bytes) | kind(u8) | threshold(u64)
+/// | owner lock hash(32 bytes) | flags(u8, optional)
+///
+/// The owner lock hash is mandatory, because the time of the oracle is public and a cell locked
+/// only by the time could be spent by anyone once the threshold is reached. The ring median flag
+/// compares the threshold with the median of the ring instead of the latest time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLockArgs {
+    pub info_type_hash: [u8; 32],
+    pub kind: TimeKind,
+    pub threshold: u64,
+    pub owner_lock_hash: [u8; 32],
+    pub flags: u8,
+}
+
+impl TimeLockArgs {
+    pub fn new(
+        info_type_hash: [u8; 32],
+        kind: TimeKind,
+        threshold: u64,
+        owner_lock_hash: [u8; 32],
+    ) -> Self {
+        TimeLockArgs {
+            info_type_hash,
+            kind,
+            threshold,
+            owner_lock_hash,
+            flags: 0,
+        }
+    }
+
+    pub fn from_slice(args: &[u8]) -> Result<Self, Error> {
+        let flags = match args.len() {
+            TIME_LOCK_ARGS_LEN => 0,
+            TIME_LOCK_ARGS_WITH_FLAGS_LEN => args[TIME_LOCK_ARGS_LEN],
+            _ => return Err(Error::InvalidTimeLockArgs),
+        };
+        if flags & !TIME_LOCK_RING_MEDIAN_FLAG != 0 {
+            return Err(Error::InvalidTimeLockArgs);
+        }
+        let mut info_type_hash = [0u8; 32];
+        info_type_hash.copy_from_slice(&args[..32]);
+        let mut owner_lock_hash = [0u8; 32];
+        owner_lock_hash.copy_from_slice(&args[41..73]);
+        Ok(TimeLockArgs {
+            info_type_hash,
+            kind: TimeKind::from_u8(args[32])?,
+            threshold: u64_from_be_slice(&args[33..41]),
+            owner_lock_hash,
+            flags,
+        })
+    }
+
+    /// The flags are omitted when they are zero
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut args = Vec::with_capacity(TIME_LOCK_ARGS_WITH_FLAGS_LEN);
+        args.extend_from_slice(&self.info_type_hash);
+        args.push(self.kind.as_u8());
+        args.extend_from_slice(&self.threshold.to_be_bytes());
+        args.extend_from_slice(&self.owner_lock_hash);
+        if self.flags != 0 {
+            args.push(self.flags);
+        }
+        args
+    }
+
+    pub fn has_ring_median(&self) -> bool {
+        self.flags & TIME_LOCK_RING_MEDIAN_FLAG != 0
+    }
+
+    /// The threshold in the unit of the versioned layout, so the timestamp is in milliseconds
+    pub fn threshold_time(&self) -> TimeValue {
+        TimeValue::from_kind(self.kind, self.threshold)
+    }
+}
+
+/// Time window type args: info type script hash(32 bytes) | kind(u8) | start(u64) | end(u64)
+///
+/// The start can't be bigger than the end, and both of them are in the unit of the versioned
+/// layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeWindowArgs {
+    pub info_type_hash: [u8; 32],
+    pub kind: TimeKind,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl TimeWindowArgs {
+    pub fn new(info_type_hash: [u8; 32], kind: TimeKind, start: u64, end: u64) -> Self {
+        TimeWindowArgs {
+            info_type_hash,
+            kind,
+            start,
+            end,
+        }
+    }
+
+    pub fn from_slice(args: &[u8]) -> Result<Self, Error> {
+        if args.len() != TIME_WINDOW_TYPE_ARGS_LEN {
+            return Err(Error::InvalidTimeWindowArgs);
+        }
+        let mut info_type_hash = [0u8; 32];
+        info_type_hash.copy_from_slice(&args[..32]);
+        let time_window_args = TimeWindowArgs {
+            info_type_hash,
+            kind: TimeKind::from_u8(args[32])?,
+            start: u64_from_be_slice(&args[33..41]),
+            end: u64_from_be_slice(&args[41..49]),
+        };
+        if time_window_args
+            .start_time()
+            .is_bigger_than(&time_window_args.end_time())
+        {
+            return Err(Error::InvalidTimeWindowArgs);
+        }
+        Ok(time_window_args)
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut args = Vec::with_capacity(TIME_WINDOW_TYPE_ARGS_LEN);
+        args.extend_from_slice(&self.info_type_hash);
+        args.push(self.kind.as_u8());
+        args.extend_from_slice(&self.start.to_be_bytes());
+        args.extend_from_slice(&self.end.to_be_bytes());
+        args
+    }
+
+    pub fn start_time(&self) -> TimeValue {
+        TimeValue::from_kind(self.kind, self.start)
+    }
+
+    pub fn end_time(&self) -> TimeValue {
+        TimeValue::from_kind(self.kind, self.end)
+    }
+}
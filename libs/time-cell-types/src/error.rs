@@ -0,0 +1,41 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IndexStateDataLenError,
+    TimeInfoDataLenError,
+    InvalidTimeInfoVersion,
+    InvalidTimeInfoKind,
+    InvalidTimeInfoEpoch,
+    InvalidInfoTypeArgs,
+    InvalidIndexStateVersion,
+    InvalidIndexStateTypeArgs,
+    TimeInfoRingIncomplete,
+    TimeInfoKindNotSame,
+    InvalidTimeLockArgs,
+    InvalidTimeWindowArgs,
+    IndexStateUpdatersEmpty,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Error::IndexStateDataLenError => "invalid time index state cell data length",
+            Error::TimeInfoDataLenError => "invalid time info cell data length",
+            Error::InvalidTimeInfoVersion => "invalid time info cell data version",
+            Error::InvalidTimeInfoKind => "invalid time info cell data kind",
+            Error::InvalidTimeInfoEpoch => "invalid time info cell data epoch",
+            Error::InvalidInfoTypeArgs => "invalid time info type args",
+            Error::InvalidIndexStateVersion => "invalid time index state cell data version",
+            Error::InvalidIndexStateTypeArgs => "invalid time index state type args",
+            Error::TimeInfoRingIncomplete => "time info cells are not all the slots of the ring",
+            Error::TimeInfoKindNotSame => "time info cells have different kinds",
+            Error::InvalidTimeLockArgs => "invalid time lock args",
+            Error::InvalidTimeWindowArgs => "invalid time window type args",
+            Error::IndexStateUpdatersEmpty => "time index state cell data has no updater",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,71 @@
+use crate::BLAKE160_LEN;
+use blake2b_ref::{Blake2b, Blake2bBuilder};
+
+pub const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
+
+/// The blake2b hasher with the same parameters as CKB
+pub fn new_blake2b() -> Blake2b {
+    Blake2bBuilder::new(32)
+        .personal(CKB_HASH_PERSONALIZATION)
+        .build()
+}
+
+pub fn blake2b_256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = new_blake2b();
+    hasher.update(data);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// The first 20 bytes of the blake2b hash, which is the same as the pubkey hash of the
+/// secp256k1 lock
+pub fn blake160(data: &[u8]) -> [u8; 20] {
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&blake2b_256(data)[..BLAKE160_LEN]);
+    hash
+}
+
+/// The message which the operators sign for a time info update:
+/// blake2b(out point of the time info input | output time info cell data)
+pub fn info_update_message(input_out_point: &[u8], output_info_data: &[u8]) -> [u8; 32] {
+    let mut hasher = new_blake2b();
+    hasher.update(input_out_point);
+    hasher.update(output_info_data);
+    let mut message = [0u8; 32];
+    hasher.finalize(&mut message);
+    message
+}
+
+/// The standard Type ID of CKB: blake2b(the first CellInput of the transaction
+/// | the index of the output cell(u64, little endian))
+pub fn type_id(first_input: &[u8], output_index: u64) -> [u8; 32] {
+    let mut hasher = new_blake2b();
+    hasher.update(first_input);
+    hasher.update(&output_index.to_le_bytes());
+    let mut type_id = [0u8; 32];
+    hasher.finalize(&mut type_id);
+    type_id
+}
+
+/// The script hash of CKB: blake2b(molecule Script { code_hash, hash_type, args }), so the
+/// type script hash of the cells can be calculated without the CKB types
+pub fn script_hash(code_hash: &[u8; 32], hash_type: u8, args: &[u8]) -> [u8; 32] {
+    // The header of the molecule table is total_size(u32) | offsets of the 3 fields(u32 * 3),
+    // and the args are a fixvec of bytes: item_count(u32) | bytes
+    let header_len = 4 * 4;
+    let args_offset = header_len + 32 + 1;
+    let total_size = args_offset + 4 + args.len();
+    let mut hasher = new_blake2b();
+    hasher.update(&(total_size as u32).to_le_bytes());
+    hasher.update(&(header_len as u32).to_le_bytes());
+    hasher.update(&(header_len as u32 + 32).to_le_bytes());
+    hasher.update(&(args_offset as u32).to_le_bytes());
+    hasher.update(code_hash);
+    hasher.update(&[hash_type]);
+    hasher.update(&(args.len() as u32).to_le_bytes());
+    hasher.update(args);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
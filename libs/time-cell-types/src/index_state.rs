@@ -0,0 +1,162 @@
+use crate::error::Error;
+use crate::schema::{
+    Byte32, Byte32Vec, IndexStateCellData, IndexStateCellDataReader, UpdatersIndexStateCellData,
+    UpdatersIndexStateCellDataReader, VersionedIndexStateCellData,
+    VersionedIndexStateCellDataReader,
+};
+use crate::{
+    INDEX_STATE_CELL_DATA_LEN, INDEX_STATE_DATA_VERSION, INDEX_STATE_DATA_WITH_UPDATERS_VERSION,
+    LEGACY_DATA_VERSION,
+};
+use alloc::{vec, vec::Vec};
+use molecule::prelude::*;
+
+/// Time index state cell data has three layouts:
+/// legacy: index(u8) | sum_of_time_info_cells(u8)
+/// version 1: version(u8) | index(u8) | sum_of_time_info_cells(u8)
+/// version 2: version(u8) | UpdatersIndexStateCellData(index, sum_of_time_info_cells, updaters)
+///
+/// The version of the legacy layout is zero, and the updaters are the lock hashes which are
+/// authorized to update the time cells. The version 2 must have at least one updater, and the
+/// cells without updaters can be updated by everyone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexStateData {
+    pub version: u8,
+    pub index: u8,
+    pub sum_of_time_info_cells: u8,
+    pub updaters: Vec<[u8; 32]>,
+}
+
+impl IndexStateData {
+    /// The index state data with the legacy layout
+    pub fn new(index: u8, sum_of_time_info_cells: u8) -> Self {
+        IndexStateData {
+            version: LEGACY_DATA_VERSION,
+            index,
+            sum_of_time_info_cells,
+            updaters: Vec::new(),
+        }
+    }
+
+    /// The index state data with the versioned layout without updaters
+    pub fn new_versioned(index: u8, sum_of_time_info_cells: u8) -> Self {
+        IndexStateData {
+            version: INDEX_STATE_DATA_VERSION,
+            index,
+            sum_of_time_info_cells,
+            updaters: Vec::new(),
+        }
+    }
+
+    /// The index state data with the versioned layout and the updater lock hashes
+    pub fn new_with_updaters(
+        index: u8,
+        sum_of_time_info_cells: u8,
+        updaters: Vec<[u8; 32]>,
+    ) -> Self {
+        IndexStateData {
+            version: INDEX_STATE_DATA_WITH_UPDATERS_VERSION,
+            index,
+            sum_of_time_info_cells,
+            updaters,
+        }
+    }
+
+    /// Only the layout is checked here, the index and the sum are checked by the scripts.
+    /// The legacy layout is told apart by its length, otherwise the first byte is the version
+    /// and the zero version means that the data has no version header.
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        if data.len() == INDEX_STATE_CELL_DATA_LEN {
+            let reader = IndexStateCellDataReader::new_unchecked(data);
+            return Ok(IndexStateData::new(
+                reader.index().as_slice()[0],
+                reader.sum_of_time_info_cells().as_slice()[0],
+            ));
+        }
+        match data.first() {
+            None | Some(&LEGACY_DATA_VERSION) => Err(Error::IndexStateDataLenError),
+            Some(&INDEX_STATE_DATA_VERSION) => {
+                let reader = VersionedIndexStateCellDataReader::from_slice(data)
+                    .map_err(|_| Error::IndexStateDataLenError)?;
+                Ok(IndexStateData::new_versioned(
+                    reader.index().as_slice()[0],
+                    reader.sum_of_time_info_cells().as_slice()[0],
+                ))
+            }
+            Some(&INDEX_STATE_DATA_WITH_UPDATERS_VERSION) => {
+                let reader = UpdatersIndexStateCellDataReader::from_slice(&data[1..])
+                    .map_err(|_| Error::IndexStateDataLenError)?;
+                if reader.updaters().is_empty() {
+                    return Err(Error::IndexStateUpdatersEmpty);
+                }
+                let updaters = reader
+                    .updaters()
+                    .iter()
+                    .map(|lock_hash| {
+                        let mut buf = [0u8; 32];
+                        buf.copy_from_slice(lock_hash.raw_data());
+                        buf
+                    })
+                    .collect();
+                Ok(IndexStateData::new_with_updaters(
+                    reader.index().as_slice()[0],
+                    reader.sum_of_time_info_cells().as_slice()[0],
+                    updaters,
+                ))
+            }
+            Some(_) => Err(Error::InvalidIndexStateVersion),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        if self.version == INDEX_STATE_DATA_WITH_UPDATERS_VERSION {
+            let updaters = Byte32Vec::new_builder()
+                .extend(
+                    self.updaters
+                        .iter()
+                        .map(|lock_hash| Byte32::new_unchecked(lock_hash.to_vec().into())),
+                )
+                .build();
+            let mut data = vec![self.version];
+            data.extend_from_slice(
+                UpdatersIndexStateCellData::new_builder()
+                    .index(Byte::new(self.index))
+                    .sum_of_time_info_cells(Byte::new(self.sum_of_time_info_cells))
+                    .updaters(updaters)
+                    .build()
+                    .as_slice(),
+            );
+            return data;
+        }
+        if self.version == LEGACY_DATA_VERSION {
+            return IndexStateCellData::new_builder()
+                .index(Byte::new(self.index))
+                .sum_of_time_info_cells(Byte::new(self.sum_of_time_info_cells))
+                .build()
+                .as_slice()
+                .to_vec();
+        }
+        VersionedIndexStateCellData::new_builder()
+            .version(Byte::new(self.version))
+            .index(Byte::new(self.index))
+            .sum_of_time_info_cells(Byte::new(self.sum_of_time_info_cells))
+            .build()
+            .as_slice()
+            .to_vec()
+    }
+
+    /// The index after the next update, which goes back to zero at the end of the ring
+    pub fn next_index(&self) -> u8 {
+        if self.index as u16 + 1 >= self.sum_of_time_info_cells as u16 {
+            0
+        } else {
+            self.index + 1
+        }
+    }
+
+    /// Everyone can update the time cells if there is no updater, which only happens to the
+    /// layouts without the updaters
+    pub fn is_updater_authorized(&self, lock_hash: &[u8; 32]) -> bool {
+        self.updaters.is_empty() || self.updaters.contains(lock_hash)
+    }
+}
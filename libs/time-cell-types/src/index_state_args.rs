@@ -0,0 +1,135 @@
+use crate::error::Error;
+use crate::{
+    INDEX_STATE_TYPE_ARGS_WITH_INFO_CODE_LEN, INDEX_STATE_TYPE_ARGS_WITH_OWNER_LEN,
+    INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_INFO_CODE_LEN, INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_OWNER_LEN,
+    INFO_TYPE_CODE_LEN, OUT_POINT_LEN, TYPE_ID_LEN,
+};
+use alloc::vec::Vec;
+
+/// The identity of the time index state cell, which is chosen when the cell is created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexStateId {
+    /// The out point of one of the creating inputs(36 bytes)
+    OutPoint([u8; 36]),
+    /// The standard Type ID of CKB(32 bytes), see `type_id`
+    TypeId([u8; 32]),
+}
+
+/// The code of the time info type script: code_hash(32 bytes) | hash_type(u8)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InfoTypeCode {
+    pub code_hash: [u8; 32],
+    pub hash_type: u8,
+}
+
+impl InfoTypeCode {
+    pub fn new(code_hash: [u8; 32], hash_type: u8) -> Self {
+        InfoTypeCode {
+            code_hash,
+            hash_type,
+        }
+    }
+
+    pub fn is_code_of(&self, code_hash: &[u8], hash_type: u8) -> bool {
+        self.code_hash[..] == code_hash[..] && self.hash_type == hash_type
+    }
+}
+
+/// Time index state type args: out point of one of the creating inputs(36 bytes) or Type ID
+/// (32 bytes) | info type code_hash(32 bytes, optional) | info type hash_type(u8, optional)
+/// | owner lock hash(32 bytes, optional)
+///
+/// The committed info type code tells the real time info cells of the oracle apart from any
+/// other cells whose type args start with the index state type hash, so the index state type
+/// script can count them. The owner lock hash authorizes the burn of the time index state cell
+/// together with all of its time info cells and the ring-size migration, so it requires the
+/// info type code, and the cells without the owner keep the legacy destroy rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexStateTypeArgs {
+    pub id: IndexStateId,
+    pub info_type_code: Option<InfoTypeCode>,
+    pub owner_lock_hash: Option<[u8; 32]>,
+}
+
+impl IndexStateTypeArgs {
+    pub fn new(
+        out_point: [u8; 36],
+        info_type_code: Option<InfoTypeCode>,
+        owner_lock_hash: Option<[u8; 32]>,
+    ) -> Self {
+        IndexStateTypeArgs {
+            id: IndexStateId::OutPoint(out_point),
+            info_type_code,
+            owner_lock_hash,
+        }
+    }
+
+    pub fn new_type_id(
+        type_id: [u8; 32],
+        info_type_code: Option<InfoTypeCode>,
+        owner_lock_hash: Option<[u8; 32]>,
+    ) -> Self {
+        IndexStateTypeArgs {
+            id: IndexStateId::TypeId(type_id),
+            info_type_code,
+            owner_lock_hash,
+        }
+    }
+
+    pub fn from_slice(args: &[u8]) -> Result<Self, Error> {
+        let (id, optional_data) = match args.len() {
+            OUT_POINT_LEN
+            | INDEX_STATE_TYPE_ARGS_WITH_INFO_CODE_LEN
+            | INDEX_STATE_TYPE_ARGS_WITH_OWNER_LEN => {
+                let mut out_point = [0u8; 36];
+                out_point.copy_from_slice(&args[..OUT_POINT_LEN]);
+                (IndexStateId::OutPoint(out_point), &args[OUT_POINT_LEN..])
+            }
+            TYPE_ID_LEN
+            | INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_INFO_CODE_LEN
+            | INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_OWNER_LEN => {
+                let mut type_id = [0u8; 32];
+                type_id.copy_from_slice(&args[..TYPE_ID_LEN]);
+                (IndexStateId::TypeId(type_id), &args[TYPE_ID_LEN..])
+            }
+            _ => return Err(Error::InvalidIndexStateTypeArgs),
+        };
+
+        let info_type_code = optional_data.get(..INFO_TYPE_CODE_LEN).map(|code_data| {
+            let mut code_hash = [0u8; 32];
+            code_hash.copy_from_slice(&code_data[..32]);
+            InfoTypeCode::new(code_hash, code_data[32])
+        });
+        let owner_lock_hash = optional_data
+            .get(INFO_TYPE_CODE_LEN..)
+            .filter(|owner_data| !owner_data.is_empty())
+            .map(|owner_data| {
+                let mut owner_lock_hash = [0u8; 32];
+                owner_lock_hash.copy_from_slice(owner_data);
+                owner_lock_hash
+            });
+        Ok(IndexStateTypeArgs {
+            id,
+            info_type_code,
+            owner_lock_hash,
+        })
+    }
+
+    /// The owner lock hash is written after the info type code, so the args with the owner lock
+    /// hash but without the info type code can't be parsed
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut args = Vec::with_capacity(INDEX_STATE_TYPE_ARGS_WITH_OWNER_LEN);
+        match self.id {
+            IndexStateId::OutPoint(out_point) => args.extend_from_slice(&out_point),
+            IndexStateId::TypeId(type_id) => args.extend_from_slice(&type_id),
+        }
+        if let Some(info_type_code) = self.info_type_code {
+            args.extend_from_slice(&info_type_code.code_hash);
+            args.push(info_type_code.hash_type);
+        }
+        if let Some(owner_lock_hash) = self.owner_lock_hash {
+            args.extend_from_slice(&owner_lock_hash);
+        }
+        args
+    }
+}
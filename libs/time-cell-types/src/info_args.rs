@@ -0,0 +1,132 @@
+use crate::error::Error;
+use crate::{
+    u64_from_be_slice, BLAKE160_LEN, HEADER_DEP_PROOF_FLAG, INDEX_STATE_TYPE_HASH_LEN,
+    INFO_TYPE_ARGS_WITH_FLAGS_LEN, INFO_TYPE_ARGS_WITH_STEPS_LEN, MULTI_SIG_FLAG, OUT_POINT_LEN,
+};
+use alloc::vec::Vec;
+
+/// Time info type args: index state type script hash(32 bytes) | flags(u8, optional)
+/// | min_step(u64, optional) | max_step(u64, optional) | threshold(u8, optional)
+/// | operator pubkey hashes(20 bytes * N, optional)
+///
+/// The steps are in the unit of the versioned time info, so the step of the legacy timestamp is
+/// in milliseconds too. The max_step of zero means that there is no upper limit of the step, and
+/// the threshold and the operators only exist with the multi-signature flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfoTypeArgs {
+    pub index_state_type_hash: [u8; 32],
+    pub flags: u8,
+    pub min_step: u64,
+    pub max_step: u64,
+    pub threshold: u8,
+    pub operators: Vec<[u8; 20]>,
+}
+
+impl InfoTypeArgs {
+    pub fn new(index_state_type_hash: [u8; 32]) -> Self {
+        InfoTypeArgs {
+            index_state_type_hash,
+            flags: 0,
+            min_step: 0,
+            max_step: 0,
+            threshold: 0,
+            operators: Vec::new(),
+        }
+    }
+
+    pub fn from_slice(args: &[u8]) -> Result<Self, Error> {
+        let (flags, min_step, max_step) = match args.len() {
+            INDEX_STATE_TYPE_HASH_LEN => (0, 0, 0),
+            INFO_TYPE_ARGS_WITH_FLAGS_LEN => (args[INDEX_STATE_TYPE_HASH_LEN], 0, 0),
+            len if len >= INFO_TYPE_ARGS_WITH_STEPS_LEN => (
+                args[INDEX_STATE_TYPE_HASH_LEN],
+                u64_from_be_slice(&args[33..41]),
+                u64_from_be_slice(&args[41..49]),
+            ),
+            _ => return Err(Error::InvalidInfoTypeArgs),
+        };
+        if flags & !(HEADER_DEP_PROOF_FLAG | MULTI_SIG_FLAG) != 0 {
+            return Err(Error::InvalidInfoTypeArgs);
+        }
+        if max_step > 0 && min_step > max_step {
+            return Err(Error::InvalidInfoTypeArgs);
+        }
+        let (threshold, operators) = if flags & MULTI_SIG_FLAG != 0 {
+            match args.get(INFO_TYPE_ARGS_WITH_STEPS_LEN..) {
+                Some(operators_data) => parse_operators(operators_data)?,
+                None => return Err(Error::InvalidInfoTypeArgs),
+            }
+        } else if args.len() > INFO_TYPE_ARGS_WITH_STEPS_LEN {
+            return Err(Error::InvalidInfoTypeArgs);
+        } else {
+            (0, Vec::new())
+        };
+        let mut index_state_type_hash = [0u8; 32];
+        index_state_type_hash.copy_from_slice(&args[..INDEX_STATE_TYPE_HASH_LEN]);
+        Ok(InfoTypeArgs {
+            index_state_type_hash,
+            flags,
+            min_step,
+            max_step,
+            threshold,
+            operators,
+        })
+    }
+
+    /// The optional fields are omitted when they are all zero
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut args = Vec::with_capacity(
+            INFO_TYPE_ARGS_WITH_STEPS_LEN + 1 + BLAKE160_LEN * self.operators.len(),
+        );
+        args.extend_from_slice(&self.index_state_type_hash);
+        if self.min_step > 0 || self.max_step > 0 || self.has_multi_sig() {
+            args.push(self.flags);
+            args.extend_from_slice(&self.min_step.to_be_bytes());
+            args.extend_from_slice(&self.max_step.to_be_bytes());
+        } else if self.flags > 0 {
+            args.push(self.flags);
+        }
+        if self.has_multi_sig() {
+            args.push(self.threshold);
+            for operator in self.operators.iter() {
+                args.extend_from_slice(operator);
+            }
+        }
+        args
+    }
+
+    /// The legacy info type args are equal to the out point args of the time index state type
+    /// script, which have neither the flags nor the steps, so the time index state cell is found
+    /// by its args instead of its type hash
+    pub fn is_legacy(args: &[u8]) -> bool {
+        args.len() == OUT_POINT_LEN
+    }
+
+    pub fn has_header_dep_proof(&self) -> bool {
+        self.flags & HEADER_DEP_PROOF_FLAG != 0
+    }
+
+    pub fn has_multi_sig(&self) -> bool {
+        self.flags & MULTI_SIG_FLAG != 0
+    }
+}
+
+// threshold(u8) | operator pubkey hashes(20 bytes * N), and 1 <= threshold <= N
+fn parse_operators(data: &[u8]) -> Result<(u8, Vec<[u8; 20]>), Error> {
+    if data.is_empty() || (data.len() - 1) % BLAKE160_LEN != 0 {
+        return Err(Error::InvalidInfoTypeArgs);
+    }
+    let threshold = data[0];
+    let operators: Vec<[u8; 20]> = data[1..]
+        .chunks(BLAKE160_LEN)
+        .map(|chunk| {
+            let mut operator = [0u8; 20];
+            operator.copy_from_slice(chunk);
+            operator
+        })
+        .collect();
+    if threshold == 0 || threshold as usize > operators.len() {
+        return Err(Error::InvalidInfoTypeArgs);
+    }
+    Ok((threshold, operators))
+}
@@ -0,0 +1,93 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod consumer_args;
+mod error;
+mod hash;
+mod index_state;
+mod index_state_args;
+mod info_args;
+mod ring_time;
+pub mod rules;
+pub mod schema;
+mod time_info;
+
+pub use consumer_args::{TimeLockArgs, TimeWindowArgs};
+pub use error::Error;
+pub use hash::{
+    blake160, blake2b_256, info_update_message, new_blake2b, script_hash, type_id,
+    CKB_HASH_PERSONALIZATION,
+};
+pub use index_state::IndexStateData;
+pub use index_state_args::{IndexStateId, IndexStateTypeArgs, InfoTypeCode};
+pub use info_args::InfoTypeArgs;
+pub use ring_time::RingTime;
+pub use time_info::{epoch_with_fraction, unpack_epoch, TimeInfoData, TimeKind, TimeValue};
+
+// The legacy layouts have no version header, and their version is regarded as zero
+pub const LEGACY_DATA_VERSION: u8 = 0;
+
+// Legacy time index state cell data: index(u8) | sum_of_time_info_cells(u8)
+pub const INDEX_STATE_CELL_DATA_LEN: usize = 2;
+
+// Versioned time index state cell data: version(u8) | index(u8) | sum_of_time_info_cells(u8)
+pub const VERSIONED_INDEX_STATE_CELL_DATA_LEN: usize = 3;
+pub const INDEX_STATE_DATA_VERSION: u8 = 1;
+// Time index state cell data of version 2: version(u8) | UpdatersIndexStateCellData(molecule table)
+pub const INDEX_STATE_DATA_WITH_UPDATERS_VERSION: u8 = 2;
+
+// Time index state type args: out point(36 bytes) or Type ID(32 bytes)
+//                             | info type code_hash(32 bytes, optional)
+//                             | info type hash_type(u8, optional)
+//                             | owner lock hash(32 bytes, optional)
+pub const OUT_POINT_LEN: usize = 36;
+pub const INDEX_STATE_TYPE_ARGS_WITH_INFO_CODE_LEN: usize = 69;
+pub const INDEX_STATE_TYPE_ARGS_WITH_OWNER_LEN: usize = 101;
+pub const TYPE_ID_LEN: usize = 32;
+pub const INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_INFO_CODE_LEN: usize = 65;
+pub const INDEX_STATE_TYPE_ARGS_WITH_TYPE_ID_OWNER_LEN: usize = 97;
+pub const INFO_TYPE_CODE_LEN: usize = 33;
+
+// Legacy time info cell data: index(u8) | timestamp(u32, seconds) or block number(u64)
+pub const TIMESTAMP_DATA_LEN: usize = 5;
+pub const BLOCK_NUMBER_DATA_LEN: usize = 9;
+
+// Versioned time info cell data: version(u8) | index(u8) | kind(u8) | time(u64)
+pub const VERSIONED_INFO_DATA_LEN: usize = 11;
+pub const INFO_DATA_VERSION: u8 = 1;
+pub const BLOCK_NUMBER_KIND: u8 = 0;
+pub const TIMESTAMP_KIND: u8 = 1;
+pub const EPOCH_KIND: u8 = 2;
+
+// Time info type args: index state type script hash(32 bytes) | flags(u8, optional)
+//                      | min_step(u64, optional) | max_step(u64, optional)
+//                      | threshold(u8, optional) | operator pubkey hashes(20 bytes * N, optional)
+pub const INDEX_STATE_TYPE_HASH_LEN: usize = 32;
+pub const INFO_TYPE_ARGS_WITH_FLAGS_LEN: usize = 33;
+pub const INFO_TYPE_ARGS_WITH_STEPS_LEN: usize = 49;
+pub const HEADER_DEP_PROOF_FLAG: u8 = 1;
+pub const MULTI_SIG_FLAG: u8 = 2;
+
+// Time lock args: info type script hash(32 bytes) | kind(u8) | threshold(u64)
+//                 | owner lock hash(32 bytes) | flags(u8, optional)
+pub const TIME_LOCK_ARGS_LEN: usize = 73;
+pub const TIME_LOCK_ARGS_WITH_FLAGS_LEN: usize = 74;
+pub const TIME_LOCK_RING_MEDIAN_FLAG: u8 = 1;
+
+// Time window type args: info type script hash(32 bytes) | kind(u8) | start(u64) | end(u64)
+pub const TIME_WINDOW_TYPE_ARGS_LEN: usize = 49;
+
+// Recoverable secp256k1 signature: r(32 bytes) | s(32 bytes) | recovery id(u8)
+pub const SIGNATURE_LEN: usize = 65;
+pub const BLAKE160_LEN: usize = 20;
+
+pub const SINCE_TIMESTAMP_BASE: u64 = 1 << 62;
+pub const SINCE_EPOCH_BASE: u64 = 1 << 61;
+pub const MILLIS_PER_SECOND: u64 = 1000;
+
+pub(crate) fn u64_from_be_slice(data: &[u8]) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(data);
+    u64::from_be_bytes(buf)
+}
@@ -0,0 +1,73 @@
+use crate::error::Error;
+use crate::index_state::IndexStateData;
+use crate::time_info::{TimeInfoData, TimeValue};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The aggregate views of all the time info cells of the ring. The median is much less
+/// harmed than the latest time by a single bad update of the time info cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingTime {
+    /// The time of the time info cell whose index is equal to the index of the time index state
+    pub latest: TimeValue,
+    pub min: TimeValue,
+    pub max: TimeValue,
+    /// The lower median if the sum of the time info cells is even, so the median is always one
+    /// of the reported times
+    pub median: TimeValue,
+    /// The step from the min to the max, and the step of epoch is the difference between the
+    /// epoch numbers
+    pub spread: u64,
+}
+
+impl RingTime {
+    /// Every slot of the ring must be filled by exactly one of the time infos, and the legacy
+    /// timestamps in seconds are converted to milliseconds
+    pub fn from_infos(infos: &[TimeInfoData], index_state: &IndexStateData) -> Result<Self, Error> {
+        let sum = index_state.sum_of_time_info_cells as usize;
+        if infos.len() != sum {
+            return Err(Error::TimeInfoRingIncomplete);
+        }
+        let mut slots = [false; 256];
+        for info in infos {
+            let index = info.index() as usize;
+            if index >= sum || slots[index] {
+                return Err(Error::TimeInfoRingIncomplete);
+            }
+            slots[index] = true;
+        }
+
+        let latest = infos
+            .iter()
+            .find(|info| info.index() == index_state.index)
+            .ok_or(Error::TimeInfoRingIncomplete)?
+            .value()
+            .to_versioned();
+        let mut values: Vec<TimeValue> = infos
+            .iter()
+            .map(|info| info.value().to_versioned())
+            .collect();
+        if values.iter().any(|value| !value.is_same_kind(&latest)) {
+            return Err(Error::TimeInfoKindNotSame);
+        }
+        values.sort_by(|value, other_value| {
+            if value.is_bigger_than(other_value) {
+                Ordering::Greater
+            } else if other_value.is_bigger_than(value) {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        });
+
+        let min = values[0];
+        let max = values[values.len() - 1];
+        Ok(RingTime {
+            latest,
+            min,
+            max,
+            median: values[(values.len() - 1) / 2],
+            spread: max.step_from(&min),
+        })
+    }
+}
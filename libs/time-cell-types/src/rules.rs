@@ -0,0 +1,186 @@
+use crate::index_state::IndexStateData;
+use crate::info_args::InfoTypeArgs;
+use crate::time_info::TimeInfoData;
+use alloc::vec;
+
+/// The rules of the index state type and info type scripts which only depend on the decoded
+/// time cells. They are called by the contracts, the SDK and the CLI, so the off-chain builders
+/// and checkers reject the same transactions as the contracts. The scripts, the locks, the since
+/// and the header deps are left to the callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleError {
+    // The sum_of_time_info_cells of the time index state cell is zero
+    TimeInfoAmountError,
+    TimeIndexOutOfBound,
+    TimeIndexIncreaseError,
+    TimeIndexChangedInMigration,
+    IndexStateVersionDowngrade,
+    TimeInfoVersionDowngrade,
+    TimeInfoDataLenError,
+    TimeInfoIndexOutOfBound,
+    TimeInfoIndexNotSame,
+    TimeInfoSlotChanged,
+    TimeInfoSlotDuplicated,
+    TimeInfoSlotMissing,
+    TimeInfoKindNotSame,
+    TimeNotBigger,
+    TimeInfoStepTooSmall,
+    TimeInfoStepTooLarge,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            RuleError::TimeInfoAmountError => "sum of time info cells is zero",
+            RuleError::TimeIndexOutOfBound => "time index out of bound",
+            RuleError::TimeIndexIncreaseError => "time index is not the next index",
+            RuleError::TimeIndexChangedInMigration => "time index changed in migration",
+            RuleError::IndexStateVersionDowngrade => "time index state version downgrade",
+            RuleError::TimeInfoVersionDowngrade => "time info version downgrade",
+            RuleError::TimeInfoDataLenError => "time info data length changed",
+            RuleError::TimeInfoIndexOutOfBound => "time info index out of bound",
+            RuleError::TimeInfoIndexNotSame => {
+                "time info index is not the index of the time index state"
+            }
+            RuleError::TimeInfoSlotChanged => "time info slot changed",
+            RuleError::TimeInfoSlotDuplicated => "time info slot duplicated",
+            RuleError::TimeInfoSlotMissing => "time info slot missing",
+            RuleError::TimeInfoKindNotSame => "time kind not same",
+            RuleError::TimeNotBigger => "time is not bigger than the latest time info",
+            RuleError::TimeInfoStepTooSmall => "time info step too small",
+            RuleError::TimeInfoStepTooLarge => "time info step too large",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RuleError {}
+
+/// The ring of the time index state cell can't be empty, and the index points to one of its
+/// slots
+pub fn check_index_state(index_state: &IndexStateData) -> Result<(), RuleError> {
+    if index_state.sum_of_time_info_cells == 0 {
+        return Err(RuleError::TimeInfoAmountError);
+    }
+    if index_state.index >= index_state.sum_of_time_info_cells {
+        return Err(RuleError::TimeIndexOutOfBound);
+    }
+    Ok(())
+}
+
+/// The update moves the index to the next slot of the same ring, and the legacy cell can be
+/// migrated to the versioned layout in place, but not the other way
+pub fn check_index_state_updated(
+    input_index_state: &IndexStateData,
+    output_index_state: &IndexStateData,
+) -> Result<(), RuleError> {
+    check_index_state_version(input_index_state, output_index_state)?;
+    if output_index_state.index != input_index_state.next_index() {
+        return Err(RuleError::TimeIndexIncreaseError);
+    }
+    Ok(())
+}
+
+/// The ring-size migration keeps the index, and the owner is checked by the callers
+pub fn check_index_state_migrated(
+    input_index_state: &IndexStateData,
+    output_index_state: &IndexStateData,
+) -> Result<(), RuleError> {
+    check_index_state_version(input_index_state, output_index_state)?;
+    if output_index_state.index != input_index_state.index {
+        return Err(RuleError::TimeIndexChangedInMigration);
+    }
+    Ok(())
+}
+
+fn check_index_state_version(
+    input_index_state: &IndexStateData,
+    output_index_state: &IndexStateData,
+) -> Result<(), RuleError> {
+    check_index_state(input_index_state)?;
+    check_index_state(output_index_state)?;
+    if output_index_state.version < input_index_state.version {
+        return Err(RuleError::IndexStateVersionDowngrade);
+    }
+    Ok(())
+}
+
+/// Every slot from start to end(exclusive) must be taken by exactly one of the time info
+/// indexes
+pub fn check_info_slots<I>(indexes: I, start: u8, end: u8) -> Result<(), RuleError>
+where
+    I: IntoIterator<Item = u8>,
+{
+    let mut is_taken = vec![false; end.saturating_sub(start) as usize];
+    for index in indexes {
+        if index < start || index >= end {
+            return Err(RuleError::TimeInfoIndexOutOfBound);
+        }
+        let slot = &mut is_taken[(index - start) as usize];
+        if *slot {
+            return Err(RuleError::TimeInfoSlotDuplicated);
+        }
+        *slot = true;
+    }
+    if is_taken.iter().any(|taken| !taken) {
+        return Err(RuleError::TimeInfoSlotMissing);
+    }
+    Ok(())
+}
+
+/// The time info cell of the new index of the output time index state cell is overwritten in
+/// its slot. Every decoded layout has a fixed data length, so the layouts of the same version
+/// must be the same.
+pub fn check_info_updated(
+    input_info: &TimeInfoData,
+    output_info: &TimeInfoData,
+    output_index_state: &IndexStateData,
+) -> Result<(), RuleError> {
+    if output_info.version() < input_info.version() {
+        return Err(RuleError::TimeInfoVersionDowngrade);
+    }
+    if output_info.version() == input_info.version()
+        && output_info.to_vec().len() != input_info.to_vec().len()
+    {
+        return Err(RuleError::TimeInfoDataLenError);
+    }
+    if output_info.index() >= output_index_state.sum_of_time_info_cells {
+        return Err(RuleError::TimeInfoIndexOutOfBound);
+    }
+    if output_info.index() != output_index_state.index {
+        return Err(RuleError::TimeInfoIndexNotSame);
+    }
+    if input_info.index() != output_info.index() {
+        return Err(RuleError::TimeInfoSlotChanged);
+    }
+    Ok(())
+}
+
+/// The new time must be bigger than the latest time of the ring rather than the overwritten
+/// one, and its step from the latest time must be in the range of the info type args. The steps
+/// are in the unit of the versioned layout, so the times are always compared in that unit and
+/// the legacy timestamps in seconds become milliseconds.
+pub fn check_info_time(
+    output_info: &TimeInfoData,
+    latest_info: &TimeInfoData,
+    info_type_args: &InfoTypeArgs,
+) -> Result<(), RuleError> {
+    let output_value = output_info.value().to_versioned();
+    let latest_value = latest_info.value().to_versioned();
+    if !output_value.is_same_kind(&latest_value) {
+        return Err(RuleError::TimeInfoKindNotSame);
+    }
+    if !output_value.is_bigger_than(&latest_value) {
+        return Err(RuleError::TimeNotBigger);
+    }
+    let step = output_value.step_from(&latest_value);
+    if step < info_type_args.min_step {
+        return Err(RuleError::TimeInfoStepTooSmall);
+    }
+    if info_type_args.max_step > 0 && step > info_type_args.max_step {
+        return Err(RuleError::TimeInfoStepTooLarge);
+    }
+    Ok(())
+}
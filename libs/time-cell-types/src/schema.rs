@@ -0,0 +1,35 @@
+// The generated code is from schemas/time_cell.mol and can be regenerated by `make schema`
+#![allow(clippy::all)]
+#![allow(unused_imports)]
+
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+
+include!("generated/time_cell.rs");
+
+impl From<u32> for Uint32BE {
+    fn from(value: u32) -> Self {
+        Uint32BE::new_unchecked(value.to_be_bytes().to_vec().into())
+    }
+}
+
+impl From<u64> for Uint64BE {
+    fn from(value: u64) -> Self {
+        Uint64BE::new_unchecked(value.to_be_bytes().to_vec().into())
+    }
+}
+
+impl<'r> Uint32BEReader<'r> {
+    pub fn to_u32(&self) -> u32 {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.raw_data());
+        u32::from_be_bytes(buf)
+    }
+}
+
+impl<'r> Uint64BEReader<'r> {
+    pub fn to_u64(&self) -> u64 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.raw_data());
+        u64::from_be_bytes(buf)
+    }
+}
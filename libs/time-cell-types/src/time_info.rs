@@ -0,0 +1,294 @@
+use crate::error::Error;
+use crate::schema::{
+    BlockNumberInfoCellData, BlockNumberInfoCellDataReader, TimestampInfoCellData,
+    TimestampInfoCellDataReader, VersionedInfoCellData, VersionedInfoCellDataReader,
+};
+use crate::{
+    BLOCK_NUMBER_DATA_LEN, BLOCK_NUMBER_KIND, EPOCH_KIND, INFO_DATA_VERSION, LEGACY_DATA_VERSION,
+    MILLIS_PER_SECOND, SINCE_EPOCH_BASE, SINCE_TIMESTAMP_BASE, TIMESTAMP_DATA_LEN, TIMESTAMP_KIND,
+    VERSIONED_INFO_DATA_LEN,
+};
+use alloc::vec::Vec;
+use molecule::prelude::*;
+
+const EPOCH_NUMBER_MASK: u64 = 0xFF_FFFF;
+const EPOCH_INDEX_MASK: u64 = 0xFFFF;
+const EPOCH_LENGTH_MASK: u64 = 0xFFFF;
+const EPOCH_VALUE_BITS: u64 = 56;
+
+/// The kind of the versioned time info cell data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeKind {
+    BlockNumber,
+    Timestamp,
+    Epoch,
+}
+
+impl TimeKind {
+    pub fn from_u8(kind: u8) -> Result<Self, Error> {
+        match kind {
+            BLOCK_NUMBER_KIND => Ok(TimeKind::BlockNumber),
+            TIMESTAMP_KIND => Ok(TimeKind::Timestamp),
+            EPOCH_KIND => Ok(TimeKind::Epoch),
+            _ => Err(Error::InvalidTimeInfoKind),
+        }
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            TimeKind::BlockNumber => BLOCK_NUMBER_KIND,
+            TimeKind::Timestamp => TIMESTAMP_KIND,
+            TimeKind::Epoch => EPOCH_KIND,
+        }
+    }
+}
+
+/// Time info cell data has two layouts:
+/// legacy: index(u8) | timestamp(u32, seconds) or block number(u64)
+/// versioned: version(u8) | index(u8) | kind(u8) | timestamp(u64, milliseconds),
+///            block number(u64) or epoch(u64)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInfoData {
+    Timestamp {
+        index: u8,
+        timestamp: u32,
+    },
+    BlockNumber {
+        index: u8,
+        block_number: u64,
+    },
+    Versioned {
+        index: u8,
+        kind: TimeKind,
+        time: u64,
+    },
+}
+
+impl TimeInfoData {
+    /// The legacy layouts are told apart by their lengths, otherwise the first byte is the
+    /// version and the zero version means that the data has no version header
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        match data.len() {
+            TIMESTAMP_DATA_LEN => {
+                let reader = TimestampInfoCellDataReader::from_slice(data)
+                    .map_err(|_| Error::TimeInfoDataLenError)?;
+                Ok(TimeInfoData::Timestamp {
+                    index: reader.index().as_slice()[0],
+                    timestamp: reader.timestamp().to_u32(),
+                })
+            }
+            BLOCK_NUMBER_DATA_LEN => {
+                let reader = BlockNumberInfoCellDataReader::from_slice(data)
+                    .map_err(|_| Error::TimeInfoDataLenError)?;
+                Ok(TimeInfoData::BlockNumber {
+                    index: reader.index().as_slice()[0],
+                    block_number: reader.block_number().to_u64(),
+                })
+            }
+            _ => match data.first() {
+                None | Some(&LEGACY_DATA_VERSION) => Err(Error::TimeInfoDataLenError),
+                Some(&INFO_DATA_VERSION) => {
+                    let reader = VersionedInfoCellDataReader::from_slice(data)
+                        .map_err(|_| Error::TimeInfoDataLenError)?;
+                    let kind = TimeKind::from_u8(reader.kind().as_slice()[0])?;
+                    let time = reader.time().to_u64();
+                    if kind == TimeKind::Epoch && !is_epoch_valid(time) {
+                        return Err(Error::InvalidTimeInfoEpoch);
+                    }
+                    Ok(TimeInfoData::Versioned {
+                        index: reader.index().as_slice()[0],
+                        kind,
+                        time,
+                    })
+                }
+                Some(_) => Err(Error::InvalidTimeInfoVersion),
+            },
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            TimeInfoData::Timestamp { index, timestamp } => TimestampInfoCellData::new_builder()
+                .index(Byte::new(*index))
+                .timestamp((*timestamp).into())
+                .build()
+                .as_slice()
+                .to_vec(),
+            TimeInfoData::BlockNumber {
+                index,
+                block_number,
+            } => BlockNumberInfoCellData::new_builder()
+                .index(Byte::new(*index))
+                .block_number((*block_number).into())
+                .build()
+                .as_slice()
+                .to_vec(),
+            TimeInfoData::Versioned { index, kind, time } => VersionedInfoCellData::new_builder()
+                .version(Byte::new(INFO_DATA_VERSION))
+                .index(Byte::new(*index))
+                .kind(Byte::new(kind.as_u8()))
+                .time((*time).into())
+                .build()
+                .as_slice()
+                .to_vec(),
+        }
+    }
+
+    pub fn index(&self) -> u8 {
+        match self {
+            TimeInfoData::Timestamp { index, .. } => *index,
+            TimeInfoData::BlockNumber { index, .. } => *index,
+            TimeInfoData::Versioned { index, .. } => *index,
+        }
+    }
+
+    pub fn value(&self) -> TimeValue {
+        match self {
+            TimeInfoData::Timestamp { timestamp, .. } => TimeValue::Timestamp(*timestamp),
+            TimeInfoData::BlockNumber { block_number, .. } => TimeValue::BlockNumber(*block_number),
+            TimeInfoData::Versioned { kind, time, .. } => TimeValue::from_kind(*kind, *time),
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        match self {
+            TimeInfoData::Timestamp { .. } | TimeInfoData::BlockNumber { .. } => {
+                LEGACY_DATA_VERSION
+            }
+            TimeInfoData::Versioned { .. } => INFO_DATA_VERSION,
+        }
+    }
+
+    /// The serialized data length, which is different between the legacy and versioned layouts
+    pub fn data_len(&self) -> usize {
+        match self {
+            TimeInfoData::Timestamp { .. } => TIMESTAMP_DATA_LEN,
+            TimeInfoData::BlockNumber { .. } => BLOCK_NUMBER_DATA_LEN,
+            TimeInfoData::Versioned { .. } => VERSIONED_INFO_DATA_LEN,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeValue {
+    /// Timestamp in seconds of the legacy time info cell data
+    Timestamp(u32),
+    /// Timestamp in milliseconds of the versioned time info cell data
+    TimestampMillis(u64),
+    BlockNumber(u64),
+    /// Epoch number with fraction which has the same format as the epoch of since:
+    /// number(24 bits) | index(16 bits) | length(16 bits)
+    Epoch(u64),
+}
+
+impl TimeValue {
+    /// The value of the versioned layout, whose timestamp is in milliseconds
+    pub fn from_kind(kind: TimeKind, time: u64) -> TimeValue {
+        match kind {
+            TimeKind::BlockNumber => TimeValue::BlockNumber(time),
+            TimeKind::Timestamp => TimeValue::TimestampMillis(time),
+            TimeKind::Epoch => TimeValue::Epoch(time),
+        }
+    }
+
+    /// The absolute since whose lower bound is the time, and the timestamp of since is in seconds
+    pub fn since(&self) -> u64 {
+        match self {
+            TimeValue::Timestamp(timestamp) => SINCE_TIMESTAMP_BASE + *timestamp as u64,
+            TimeValue::TimestampMillis(timestamp) => {
+                SINCE_TIMESTAMP_BASE + timestamp / MILLIS_PER_SECOND
+            }
+            TimeValue::BlockNumber(block_number) => *block_number,
+            TimeValue::Epoch(epoch) => SINCE_EPOCH_BASE + epoch,
+        }
+    }
+
+    /// Convert the value of the legacy layout to the value of the versioned layout, and the
+    /// legacy timestamp in seconds becomes the timestamp in milliseconds
+    pub fn to_versioned(&self) -> TimeValue {
+        match self {
+            TimeValue::Timestamp(timestamp) => {
+                TimeValue::TimestampMillis(*timestamp as u64 * MILLIS_PER_SECOND)
+            }
+            _ => *self,
+        }
+    }
+
+    pub fn is_same_kind(&self, other: &TimeValue) -> bool {
+        matches!(
+            (self, other),
+            (TimeValue::Timestamp(_), TimeValue::Timestamp(_))
+                | (TimeValue::TimestampMillis(_), TimeValue::TimestampMillis(_))
+                | (TimeValue::BlockNumber(_), TimeValue::BlockNumber(_))
+                | (TimeValue::Epoch(_), TimeValue::Epoch(_))
+        )
+    }
+
+    /// Always false if the kinds of the two values are not the same
+    pub fn is_bigger_than(&self, other: &TimeValue) -> bool {
+        match (self, other) {
+            (TimeValue::Timestamp(value), TimeValue::Timestamp(other_value)) => value > other_value,
+            (TimeValue::TimestampMillis(value), TimeValue::TimestampMillis(other_value)) => {
+                value > other_value
+            }
+            (TimeValue::BlockNumber(value), TimeValue::BlockNumber(other_value)) => {
+                value > other_value
+            }
+            (TimeValue::Epoch(epoch), TimeValue::Epoch(other_epoch)) => {
+                is_epoch_bigger(*epoch, *other_epoch)
+            }
+            _ => false,
+        }
+    }
+
+    /// The step from the other value, and the step of epoch is the difference between the
+    /// epoch numbers. Zero is returned if the value is not bigger than the other one.
+    pub fn step_from(&self, other: &TimeValue) -> u64 {
+        if !self.is_bigger_than(other) {
+            return 0;
+        }
+        match (self, other) {
+            (TimeValue::Timestamp(value), TimeValue::Timestamp(other_value)) => {
+                (value - other_value) as u64
+            }
+            (TimeValue::TimestampMillis(value), TimeValue::TimestampMillis(other_value)) => {
+                value - other_value
+            }
+            (TimeValue::BlockNumber(value), TimeValue::BlockNumber(other_value)) => {
+                value - other_value
+            }
+            (TimeValue::Epoch(epoch), TimeValue::Epoch(other_epoch)) => {
+                unpack_epoch(*epoch).0 - unpack_epoch(*other_epoch).0
+            }
+            _ => 0,
+        }
+    }
+}
+
+pub fn epoch_with_fraction(number: u64, index: u64, length: u64) -> u64 {
+    (length << 40) | (index << 24) | number
+}
+
+/// Returns the number, index and length of the epoch
+pub fn unpack_epoch(epoch: u64) -> (u64, u64, u64) {
+    let number = epoch & EPOCH_NUMBER_MASK;
+    let index = (epoch >> 24) & EPOCH_INDEX_MASK;
+    let length = (epoch >> 40) & EPOCH_LENGTH_MASK;
+    (number, index, length)
+}
+
+// The epoch length must be positive and the epoch index must be less than the epoch length
+fn is_epoch_valid(epoch: u64) -> bool {
+    let (_, index, length) = unpack_epoch(epoch);
+    epoch >> EPOCH_VALUE_BITS == 0 && length > 0 && index < length
+}
+
+// Compare number + index / length of the two epochs
+fn is_epoch_bigger(epoch: u64, other_epoch: u64) -> bool {
+    let (number, index, length) = unpack_epoch(epoch);
+    let (other_number, other_index, other_length) = unpack_epoch(other_epoch);
+    if number != other_number {
+        return number > other_number;
+    }
+    index * other_length > other_index * length
+}
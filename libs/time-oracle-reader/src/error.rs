@@ -0,0 +1,62 @@
+use ckb_std::error::SysError;
+
+/// The errors of reading the time oracle from the cell deps, which can be mapped to the error
+/// codes of the consumer scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    IndexOutOfBound,
+    ItemMissing,
+    LengthNotEnough,
+    Encoding,
+    // No cell dep has the info type script of the oracle
+    TimeInfoNotFound,
+    // No cell dep has the index state type script of the info type args
+    IndexStateNotFound,
+    IndexStateDuplicated,
+    // No time info cell dep has the index of the time index state cell
+    LatestTimeInfoNotFound,
+    InvalidInfoTypeArgs,
+    InvalidIndexStateData,
+    InvalidTimeInfoData,
+    // The time info cell deps don't fill every slot of the ring exactly once
+    TimeInfoRingIncomplete,
+    TimeInfoKindNotSame,
+    // The time index state type args of the oracle don't commit the info type code
+    InfoTypeCodeNotCommitted,
+    // More than one time info cell dep has the index of the time index state cell
+    LatestTimeInfoDuplicated,
+}
+
+impl From<SysError> for Error {
+    fn from(err: SysError) -> Self {
+        use SysError::*;
+        match err {
+            IndexOutOfBound => Self::IndexOutOfBound,
+            ItemMissing => Self::ItemMissing,
+            LengthNotEnough(_) => Self::LengthNotEnough,
+            Encoding => Self::Encoding,
+            Unknown(err_code) => panic!("unexpected sys error {}", err_code),
+        }
+    }
+}
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        use time_cell_types::Error::*;
+        match err {
+            IndexStateDataLenError
+            | InvalidIndexStateVersion
+            | InvalidIndexStateTypeArgs
+            | IndexStateUpdatersEmpty => Self::InvalidIndexStateData,
+            TimeInfoDataLenError
+            | InvalidTimeInfoVersion
+            | InvalidTimeInfoKind
+            | InvalidTimeInfoEpoch => Self::InvalidTimeInfoData,
+            InvalidInfoTypeArgs => Self::InvalidInfoTypeArgs,
+            TimeInfoRingIncomplete => Self::TimeInfoRingIncomplete,
+            TimeInfoKindNotSame => Self::TimeInfoKindNotSame,
+            // The consumer args are decoded by the consumer scripts rather than the reader
+            InvalidTimeLockArgs | InvalidTimeWindowArgs => Self::Encoding,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! The on-chain reader of the time oracle for the lock and type scripts which depend on the
+//! time. The time index state cell and the time info cells of the oracle are put into the cell
+//! deps of the transaction, and the oracle is identified by the type script hash of its time
+//! info cells, so a fake cell with another type script can't be mistaken for the oracle.
+#![no_std]
+
+extern crate alloc;
+
+mod error;
+
+pub use error::Error;
+pub use time_cell_types::{IndexStateData, RingTime, TimeInfoData, TimeKind, TimeValue};
+
+use alloc::vec::Vec;
+use ckb_std::{
+    ckb_constants::Source,
+    ckb_types::{bytes::Bytes, prelude::*},
+    high_level::{load_cell_data, load_cell_type, load_cell_type_hash, QueryIter},
+};
+use time_cell_types::{script_hash, IndexStateTypeArgs, InfoTypeArgs};
+
+/// The latest timestamp, block number or epoch of the oracle
+pub fn load_latest_time(info_type_hash: &[u8; 32]) -> Result<TimeValue, Error> {
+    Ok(load_latest_time_info(info_type_hash)?.value())
+}
+
+/// The latest time of the oracle whose time info cells have the info type args, see
+/// `load_info_type_hash`
+pub fn load_latest_time_by_args(info_type_args: &[u8]) -> Result<TimeValue, Error> {
+    load_latest_time(&load_info_type_hash(info_type_args)?)
+}
+
+/// The time info cell whose index is equal to the index of the time index state cell, and the
+/// time info has been validated by the info type script when the cell was created or updated.
+/// Only one time info cell dep can have the index, otherwise the latest time is ambiguous.
+pub fn load_latest_time_info(info_type_hash: &[u8; 32]) -> Result<TimeInfoData, Error> {
+    let info_positions = find_cell_dep_positions(info_type_hash);
+    let index_state_data = load_oracle_index_state_data(&info_positions)?;
+    let mut latest_info = None;
+    for position in info_positions {
+        let info_data = load_cell_data(position, Source::CellDep)?;
+        let info = TimeInfoData::from_slice(&info_data)?;
+        if info.index() != index_state_data.index {
+            continue;
+        }
+        if latest_info.is_some() {
+            return Err(Error::LatestTimeInfoDuplicated);
+        }
+        latest_info = Some(info);
+    }
+    latest_info.ok_or(Error::LatestTimeInfoNotFound)
+}
+
+/// The type script hash of the time info cells with the info type args. Anyone can create a cell
+/// with the same type args, so the code of the info type script is the one committed by the
+/// type args of the time index state cell dep, and the oracle without the committed code can
+/// only be read by the info type script hash.
+pub fn load_info_type_hash(info_type_args: &[u8]) -> Result<[u8; 32], Error> {
+    let index_state_type_hash = InfoTypeArgs::from_slice(info_type_args)?.index_state_type_hash;
+    let index_state_position = find_index_state_position(&index_state_type_hash)?;
+    let index_state_type_args: Bytes = match load_cell_type(index_state_position, Source::CellDep)?
+    {
+        Some(index_state_type) => index_state_type.args().unpack(),
+        None => return Err(Error::IndexStateNotFound),
+    };
+    let info_type_code = IndexStateTypeArgs::from_slice(&index_state_type_args)?
+        .info_type_code
+        .ok_or(Error::InfoTypeCodeNotCommitted)?;
+    Ok(script_hash(
+        &info_type_code.code_hash,
+        info_type_code.hash_type,
+        info_type_args,
+    ))
+}
+
+/// The max, median and spread of all the time info cells of the ring, which must all be put
+/// into the cell deps. The consumers can depend on the median rather than the latest time, so
+/// that a single bad update of the oracle is much less harmful.
+pub fn load_ring_time(info_type_hash: &[u8; 32]) -> Result<RingTime, Error> {
+    let info_positions = find_cell_dep_positions(info_type_hash);
+    let index_state_data = load_oracle_index_state_data(&info_positions)?;
+    let infos = info_positions
+        .into_iter()
+        .map(|position| {
+            let info_data = load_cell_data(position, Source::CellDep)?;
+            Ok(TimeInfoData::from_slice(&info_data)?)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(RingTime::from_infos(&infos, &index_state_data)?)
+}
+
+/// The time index state cell of the oracle, which is found by the info type args of the first
+/// time info cell dep
+pub fn load_index_state_data(info_type_hash: &[u8; 32]) -> Result<IndexStateData, Error> {
+    load_oracle_index_state_data(&find_cell_dep_positions(info_type_hash))
+}
+
+fn load_oracle_index_state_data(info_positions: &[usize]) -> Result<IndexStateData, Error> {
+    let info_position = match info_positions.first() {
+        Some(position) => *position,
+        None => return Err(Error::TimeInfoNotFound),
+    };
+    let info_type_args: Bytes = match load_cell_type(info_position, Source::CellDep)? {
+        Some(info_type) => info_type.args().unpack(),
+        None => return Err(Error::TimeInfoNotFound),
+    };
+    let info_type_args = InfoTypeArgs::from_slice(&info_type_args)?;
+
+    let index_state_position = find_index_state_position(&info_type_args.index_state_type_hash)?;
+    let index_state_data = load_cell_data(index_state_position, Source::CellDep)?;
+    Ok(IndexStateData::from_slice(&index_state_data)?)
+}
+
+fn find_index_state_position(index_state_type_hash: &[u8; 32]) -> Result<usize, Error> {
+    match find_cell_dep_positions(index_state_type_hash).as_slice() {
+        [] => Err(Error::IndexStateNotFound),
+        [position] => Ok(*position),
+        _ => Err(Error::IndexStateDuplicated),
+    }
+}
+
+fn find_cell_dep_positions(type_hash: &[u8; 32]) -> Vec<usize> {
+    QueryIter::new(load_cell_type_hash, Source::CellDep)
+        .enumerate()
+        .filter(|(_, type_hash_opt)| type_hash_opt.as_ref() == Some(type_hash))
+        .map(|(position, _)| position)
+        .collect()
+}
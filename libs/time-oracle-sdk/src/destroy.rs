@@ -0,0 +1,83 @@
+use crate::error::Error;
+use crate::{build_change_output, is_info_cell_of, total_capacity, LiveCell, OracleCodes};
+use ckb_types::{
+    bytes::Bytes,
+    core::{TransactionBuilder, TransactionView},
+    packed::{CellDep, Script},
+    prelude::*,
+};
+use time_cell_types::{
+    rules::check_index_state, IndexStateData, IndexStateTypeArgs, RingTime, TimeInfoData,
+};
+
+/// The destroy transaction burns the time index state cell together with all of its time info
+/// cells, and the capacity is returned to the change lock. The owner of the index state type
+/// args must unlock one of the inputs, which can be one of the extra inputs.
+#[derive(Debug, Clone)]
+pub struct DestroyTx {
+    pub codes: OracleCodes,
+    pub lock_deps: Vec<CellDep>,
+    pub index_state_cell: LiveCell,
+    /// The time info cells of all the slots of the ring
+    pub info_cells: Vec<LiveCell>,
+    pub extra_inputs: Vec<LiveCell>,
+    pub change_lock: Script,
+    pub fee: u64,
+}
+
+impl DestroyTx {
+    pub fn build(&self) -> Result<TransactionView, Error> {
+        let index_state_type_hash = self
+            .index_state_cell
+            .type_hash()
+            .ok_or(Error::NotOracleCell)?;
+        if !self
+            .info_cells
+            .iter()
+            .all(|cell| is_info_cell_of(cell, &index_state_type_hash))
+        {
+            return Err(Error::NotOracleCell);
+        }
+
+        // Every slot must be burnt, otherwise some time info cells are left without the index
+        // state cell
+        let index_state = IndexStateData::from_slice(&self.index_state_cell.data)?;
+        check_index_state(&index_state)?;
+        let infos = self
+            .info_cells
+            .iter()
+            .map(|cell| TimeInfoData::from_slice(&cell.data))
+            .collect::<Result<Vec<_>, _>>()?;
+        RingTime::from_infos(&infos, &index_state)?;
+
+        // The time info cells of the time index state cell without an owner can't be burnt
+        let index_state_args: Bytes = match self.index_state_cell.output.type_().to_opt() {
+            Some(index_state_type) => index_state_type.args().unpack(),
+            None => return Err(Error::NotOracleCell),
+        };
+        if IndexStateTypeArgs::from_slice(&index_state_args)?
+            .owner_lock_hash
+            .is_none()
+        {
+            return Err(Error::IndexStateOwnerNotExist);
+        }
+
+        let mut cells = vec![&self.index_state_cell];
+        cells.extend(self.info_cells.iter());
+        cells.extend(self.extra_inputs.iter());
+        let change_capacity = total_capacity(cells.iter().cloned())?
+            .checked_sub(self.fee)
+            .ok_or(Error::InsufficientCapacity)?;
+        let change_output = build_change_output(&self.change_lock, change_capacity)?
+            .ok_or(Error::InsufficientCapacity)?;
+
+        Ok(TransactionBuilder::default()
+            .inputs(cells.iter().map(|cell| cell.input(0)))
+            .output(change_output)
+            .output_data(Bytes::new().pack())
+            .cell_deps(self.lock_deps.clone())
+            .cell_deps(self.codes.cell_deps())
+            .witnesses(cells.iter().map(|_| Bytes::new().pack()))
+            .build())
+    }
+}
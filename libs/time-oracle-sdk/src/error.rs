@@ -0,0 +1,56 @@
+use std::fmt;
+use time_cell_types::rules::RuleError;
+
+/// The errors of building the time oracle transactions, which are found before the transaction
+/// is sent to the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    // The cell data or the type args of a time cell is invalid
+    TimeCell(time_cell_types::Error),
+    CapacityOverflow,
+    InsufficientCapacity,
+    // The cell is not the time index state cell or the time info cell of the oracle
+    NotOracleCell,
+    // The time info cell is not the slot of the next index
+    InfoSlotNotNext,
+    // The latest time info cell is not the slot of the current index
+    InfoSlotNotLatest,
+    // The time cells break one of the rules of the contracts
+    Rule(RuleError),
+    // The info type args require the header dep proof
+    HeaderDepMissing,
+    // The time info cells of the time index state cell without an owner can't be burnt
+    IndexStateOwnerNotExist,
+}
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        Error::TimeCell(err)
+    }
+}
+
+impl From<RuleError> for Error {
+    fn from(err: RuleError) -> Self {
+        Error::Rule(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TimeCell(err) => write!(f, "{}", err),
+            Error::CapacityOverflow => write!(f, "capacity overflow"),
+            Error::InsufficientCapacity => write!(f, "insufficient capacity"),
+            Error::NotOracleCell => write!(f, "not a cell of the time oracle"),
+            Error::InfoSlotNotNext => write!(f, "time info cell is not the slot of the next index"),
+            Error::InfoSlotNotLatest => {
+                write!(f, "time info cell is not the slot of the current index")
+            }
+            Error::Rule(err) => write!(f, "{}", err),
+            Error::HeaderDepMissing => write!(f, "header dep is required by the info type args"),
+            Error::IndexStateOwnerNotExist => write!(f, "time index state cell has no owner"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,118 @@
+use crate::error::Error;
+use crate::{build_change_output, build_exact_output, total_capacity, LiveCell, OracleCodes};
+use ckb_types::{
+    bytes::Bytes,
+    core::{TransactionBuilder, TransactionView},
+    packed::{CellDep, Script},
+    prelude::*,
+};
+use time_cell_types::{
+    rules::check_index_state, type_id, IndexStateData, IndexStateTypeArgs, InfoTypeArgs, RingTime,
+    TimeInfoData,
+};
+
+/// The genesis transaction creates the time index state cell and the time info cells of all the
+/// slots. The args of the index state type script are the out point of the first funding cell,
+/// or the Type ID of the time index state cell when `type_id` is set, and the code of the info
+/// type script, and the index state type hash of the info type args is replaced by the hash of
+/// that script.
+#[derive(Debug, Clone)]
+pub struct GenesisTx {
+    pub codes: OracleCodes,
+    pub lock_deps: Vec<CellDep>,
+    pub funding_cells: Vec<LiveCell>,
+    /// The lock of the time index state cell and the time info cells
+    pub oracle_lock: Script,
+    pub owner_lock_hash: Option<[u8; 32]>,
+    /// Identify the time index state cell by the standard Type ID instead of the out point
+    pub type_id: bool,
+    pub index_state: IndexStateData,
+    pub info_type_args: InfoTypeArgs,
+    /// One time info for every slot of the ring
+    pub infos: Vec<TimeInfoData>,
+    pub change_lock: Script,
+    pub fee: u64,
+}
+
+impl GenesisTx {
+    pub fn build(&self) -> Result<TransactionView, Error> {
+        // Every slot must be filled by exactly one time info of the same kind
+        check_index_state(&self.index_state)?;
+        RingTime::from_infos(&self.infos, &self.index_state)?;
+        let first_cell = self
+            .funding_cells
+            .first()
+            .ok_or(Error::InsufficientCapacity)?;
+
+        let index_state_args = if self.type_id {
+            // The time index state cell is the output 0, and the first input is the first
+            // funding cell whose since is zero
+            IndexStateTypeArgs::new_type_id(
+                type_id(first_cell.input(0).as_slice(), 0),
+                Some(self.codes.info_type_code()),
+                self.owner_lock_hash,
+            )
+        } else {
+            let mut out_point = [0u8; 36];
+            out_point.copy_from_slice(first_cell.out_point.as_slice());
+            IndexStateTypeArgs::new(
+                out_point,
+                Some(self.codes.info_type_code()),
+                self.owner_lock_hash,
+            )
+        };
+        let index_state_type_script = self
+            .codes
+            .index_state_type
+            .script(Bytes::from(index_state_args.to_vec()));
+
+        let mut info_type_args = self.info_type_args.clone();
+        info_type_args
+            .index_state_type_hash
+            .copy_from_slice(index_state_type_script.calc_script_hash().as_slice());
+        let info_type_script = self
+            .codes
+            .info_type
+            .script(Bytes::from(info_type_args.to_vec()));
+
+        let mut outputs_data = vec![Bytes::from(self.index_state.to_vec())];
+        let mut infos = self.infos.clone();
+        infos.sort_by_key(|info| info.index());
+        outputs_data.extend(infos.iter().map(|info| Bytes::from(info.to_vec())));
+
+        let mut outputs = vec![build_exact_output(
+            self.oracle_lock.clone(),
+            Some(index_state_type_script),
+            &outputs_data[0],
+        )?];
+        for info_data in &outputs_data[1..] {
+            outputs.push(build_exact_output(
+                self.oracle_lock.clone(),
+                Some(info_type_script.clone()),
+                info_data,
+            )?);
+        }
+
+        let outputs_capacity = outputs.iter().try_fold(0u64, |total, output| {
+            let capacity: u64 = output.capacity().unpack();
+            total.checked_add(capacity).ok_or(Error::CapacityOverflow)
+        })?;
+        let change_capacity = total_capacity(&self.funding_cells)?
+            .checked_sub(outputs_capacity)
+            .and_then(|capacity| capacity.checked_sub(self.fee))
+            .ok_or(Error::InsufficientCapacity)?;
+        if let Some(change_output) = build_change_output(&self.change_lock, change_capacity)? {
+            outputs.push(change_output);
+            outputs_data.push(Bytes::new());
+        }
+
+        Ok(TransactionBuilder::default()
+            .inputs(self.funding_cells.iter().map(|cell| cell.input(0)))
+            .outputs(outputs)
+            .outputs_data(outputs_data.pack())
+            .cell_deps(self.lock_deps.clone())
+            .cell_deps(self.codes.cell_deps())
+            .witnesses(self.funding_cells.iter().map(|_| Bytes::new().pack()))
+            .build())
+    }
+}
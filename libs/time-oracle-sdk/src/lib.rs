@@ -0,0 +1,155 @@
+//! The off-chain builders of the time oracle transactions. The builders return the unsigned
+//! transactions whose cells are ordered as the contracts require, and the witnesses are left
+//! empty for the signers of the locks.
+//!
+//! - genesis: the time index state cell is the output 0 and the time info cells of all the
+//!   slots are the outputs 1..=sum_of_time_info_cells
+//! - update: the time index state cell is the input and output 0, and the time info cell of the
+//!   next index is the input and output 1, whose since is the new time. The time info cell of
+//!   the current index is the last cell dep.
+//! - destroy: the time index state cell is the input 0 and all the time info cells are the
+//!   inputs 1..=sum_of_time_info_cells
+mod destroy;
+mod error;
+mod genesis;
+mod update;
+
+pub use destroy::DestroyTx;
+pub use error::Error;
+pub use genesis::GenesisTx;
+pub use update::{info_update_message, set_operator_signatures, UpdateTx};
+
+use ckb_types::{
+    bytes::Bytes,
+    core::{Capacity, ScriptHashType},
+    packed::{Byte32, CellDep, CellInput, CellOutput, OutPoint, Script},
+    prelude::*,
+};
+use time_cell_types::InfoTypeCode;
+
+/// A live cell which is consumed by the transaction
+#[derive(Debug, Clone)]
+pub struct LiveCell {
+    pub out_point: OutPoint,
+    pub output: CellOutput,
+    pub data: Bytes,
+}
+
+impl LiveCell {
+    pub fn input(&self, since: u64) -> CellInput {
+        CellInput::new_builder()
+            .previous_output(self.out_point.clone())
+            .since(since.pack())
+            .build()
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.output.capacity().unpack()
+    }
+
+    pub fn type_hash(&self) -> Option<Byte32> {
+        self.output
+            .type_()
+            .to_opt()
+            .map(|type_script| type_script.calc_script_hash())
+    }
+}
+
+/// The deployed code of a script and the cell dep of the code
+#[derive(Debug, Clone)]
+pub struct ScriptCode {
+    pub code_hash: Byte32,
+    pub hash_type: ScriptHashType,
+    pub cell_dep: CellDep,
+}
+
+impl ScriptCode {
+    pub fn script(&self, args: Bytes) -> Script {
+        Script::new_builder()
+            .code_hash(self.code_hash.clone())
+            .hash_type(self.hash_type.into())
+            .args(args.pack())
+            .build()
+    }
+}
+
+/// The deployed index state type and info type scripts of the oracle
+#[derive(Debug, Clone)]
+pub struct OracleCodes {
+    pub index_state_type: ScriptCode,
+    pub info_type: ScriptCode,
+}
+
+impl OracleCodes {
+    /// The info type code which is committed by the time index state type args
+    pub fn info_type_code(&self) -> InfoTypeCode {
+        let mut code_hash = [0u8; 32];
+        code_hash.copy_from_slice(self.info_type.code_hash.as_slice());
+        InfoTypeCode::new(code_hash, self.info_type.hash_type as u8)
+    }
+
+    fn cell_deps(&self) -> Vec<CellDep> {
+        vec![
+            self.index_state_type.cell_dep.clone(),
+            self.info_type.cell_dep.clone(),
+        ]
+    }
+}
+
+// The output whose capacity is just enough to hold the data
+fn build_exact_output(
+    lock: Script,
+    type_opt: Option<Script>,
+    data: &[u8],
+) -> Result<CellOutput, Error> {
+    let output = CellOutput::new_builder()
+        .lock(lock)
+        .type_(type_opt.pack())
+        .build();
+    let capacity = occupied_capacity(&output, data.len())?;
+    Ok(output.as_builder().capacity(capacity.pack()).build())
+}
+
+fn occupied_capacity(output: &CellOutput, data_len: usize) -> Result<u64, Error> {
+    Capacity::bytes(data_len)
+        .and_then(|data_capacity| output.occupied_capacity(data_capacity))
+        .map(|capacity| capacity.as_u64())
+        .map_err(|_| Error::CapacityOverflow)
+}
+
+fn total_capacity<'a, I>(cells: I) -> Result<u64, Error>
+where
+    I: IntoIterator<Item = &'a LiveCell>,
+{
+    cells.into_iter().try_fold(0u64, |total, cell| {
+        total
+            .checked_add(cell.capacity())
+            .ok_or(Error::CapacityOverflow)
+    })
+}
+
+// The change output with the rest capacity, and no change output is needed if nothing is left
+fn build_change_output(lock: &Script, capacity: u64) -> Result<Option<CellOutput>, Error> {
+    if capacity == 0 {
+        return Ok(None);
+    }
+    let output = CellOutput::new_builder()
+        .capacity(capacity.pack())
+        .lock(lock.clone())
+        .build();
+    if capacity < occupied_capacity(&output, 0)? {
+        return Err(Error::InsufficientCapacity);
+    }
+    Ok(Some(output))
+}
+
+// The type args of the time info cells start with the index state type hash
+fn is_info_cell_of(cell: &LiveCell, index_state_type_hash: &Byte32) -> bool {
+    match cell.output.type_().to_opt() {
+        Some(info_type) => {
+            let info_args: Bytes = info_type.args().unpack();
+            info_args.starts_with(index_state_type_hash.as_slice())
+        }
+        None => false,
+    }
+}
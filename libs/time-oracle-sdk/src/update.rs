@@ -0,0 +1,224 @@
+use crate::error::Error;
+use crate::{
+    build_change_output, is_info_cell_of, occupied_capacity, total_capacity, LiveCell, OracleCodes,
+};
+use ckb_types::{
+    bytes::Bytes,
+    core::{TransactionBuilder, TransactionView},
+    packed::{Byte32, CellDep, CellOutput, Script, WitnessArgs},
+    prelude::*,
+};
+use time_cell_types::{
+    rules::{check_index_state_updated, check_info_time, check_info_updated, RuleError},
+    IndexStateData, InfoTypeArgs, TimeInfoData, TimeKind, TimeValue,
+};
+
+const INFO_POSITION: usize = 1;
+
+/// The update transaction moves the time index state cell to the next index and overwrites the
+/// time info cell of that slot with the new time. The genesis gives the oracle cells exactly
+/// their occupied capacity, so the fee is paid by the extra inputs, such as the wallet cells of
+/// the updater, which also refill the time info cell whose legacy data grows to the versioned
+/// layout, and the rest capacity of the extra inputs is returned to the change lock.
+#[derive(Debug, Clone)]
+pub struct UpdateTx {
+    pub codes: OracleCodes,
+    pub lock_deps: Vec<CellDep>,
+    pub index_state_cell: LiveCell,
+    /// The time info cell of the next index of the time index state cell
+    pub info_cell: LiveCell,
+    /// The time info cell of the current index of the time index state cell, which holds the
+    /// latest time and is put into the cell deps unless it's the info cell itself
+    pub latest_info_cell: LiveCell,
+    /// The legacy timestamp in seconds is migrated to the versioned layout if the time is
+    /// TimeValue::TimestampMillis
+    pub time: TimeValue,
+    /// The header whose time is equal to the new time if the info type args require the header
+    /// dep proof
+    pub header_dep: Option<Byte32>,
+    /// The cells which pay the fee
+    pub extra_inputs: Vec<LiveCell>,
+    pub change_lock: Script,
+    pub fee: u64,
+}
+
+impl UpdateTx {
+    pub fn build(&self) -> Result<TransactionView, Error> {
+        let index_state_type_hash = self
+            .index_state_cell
+            .type_hash()
+            .ok_or(Error::NotOracleCell)?;
+        if !is_info_cell_of(&self.info_cell, &index_state_type_hash)
+            || self.latest_info_cell.output.type_().as_slice()
+                != self.info_cell.output.type_().as_slice()
+        {
+            return Err(Error::NotOracleCell);
+        }
+        let info_args: Bytes = match self.info_cell.output.type_().to_opt() {
+            Some(info_type) => info_type.args().unpack(),
+            None => return Err(Error::NotOracleCell),
+        };
+        let info_type_args = InfoTypeArgs::from_slice(&info_args)?;
+
+        let input_index_state = IndexStateData::from_slice(&self.index_state_cell.data)?;
+        let mut output_index_state = input_index_state.clone();
+        output_index_state.index = input_index_state.next_index();
+        check_index_state_updated(&input_index_state, &output_index_state)?;
+
+        let input_info = TimeInfoData::from_slice(&self.info_cell.data)?;
+        if input_info.index() != output_index_state.index {
+            return Err(Error::InfoSlotNotNext);
+        }
+        let latest_info = TimeInfoData::from_slice(&self.latest_info_cell.data)?;
+        if latest_info.index() != input_index_state.index {
+            return Err(Error::InfoSlotNotLatest);
+        }
+        let output_info = build_output_info(&input_info, self.time)?;
+        check_info_updated(&input_info, &output_info, &output_index_state)?;
+        check_info_time(&output_info, &latest_info, &info_type_args)?;
+        let latest_info_deps = if self.latest_info_cell.out_point == self.info_cell.out_point {
+            vec![]
+        } else {
+            vec![CellDep::new_builder()
+                .out_point(self.latest_info_cell.out_point.clone())
+                .build()]
+        };
+
+        // The since of the time info input is the new time, unless the header dep proves it
+        let since = if info_type_args.has_header_dep_proof() {
+            0
+        } else {
+            self.time.since()
+        };
+        let header_deps = match (&self.header_dep, info_type_args.has_header_dep_proof()) {
+            (Some(header_hash), _) => vec![header_hash.clone()],
+            (None, true) => return Err(Error::HeaderDepMissing),
+            (None, false) => vec![],
+        };
+
+        let index_state_data = Bytes::from(output_index_state.to_vec());
+        let index_state_output = build_refilled_output(&self.index_state_cell, &index_state_data)?;
+        let info_data = Bytes::from(output_info.to_vec());
+        let info_output = build_refilled_output(&self.info_cell, &info_data)?;
+
+        let mut inputs = vec![self.index_state_cell.input(0), self.info_cell.input(since)];
+        inputs.extend(self.extra_inputs.iter().map(|cell| cell.input(0)));
+        let refilled_capacity = (output_capacity(&index_state_output)
+            - self.index_state_cell.capacity())
+            + (output_capacity(&info_output) - self.info_cell.capacity());
+        let change_capacity = total_capacity(&self.extra_inputs)?
+            .checked_sub(refilled_capacity)
+            .and_then(|capacity| capacity.checked_sub(self.fee))
+            .ok_or(Error::InsufficientCapacity)?;
+        let mut outputs = vec![index_state_output, info_output];
+        let mut outputs_data = vec![index_state_data, info_data];
+        if let Some(change_output) = build_change_output(&self.change_lock, change_capacity)? {
+            outputs.push(change_output);
+            outputs_data.push(Bytes::new());
+        }
+
+        let witnesses: Vec<_> = inputs.iter().map(|_| Bytes::new().pack()).collect();
+        Ok(TransactionBuilder::default()
+            .inputs(inputs)
+            .outputs(outputs)
+            .outputs_data(outputs_data.pack())
+            .cell_deps(self.lock_deps.clone())
+            .cell_deps(self.codes.cell_deps())
+            .cell_deps(latest_info_deps)
+            .header_deps(header_deps)
+            .witnesses(witnesses)
+            .build())
+    }
+}
+
+// The output of the oracle cell keeps the capacity of the input, unless the capacity is refilled
+// to hold the new data
+fn build_refilled_output(cell: &LiveCell, data: &[u8]) -> Result<CellOutput, Error> {
+    let capacity = occupied_capacity(&cell.output, data.len())?;
+    if cell.capacity() >= capacity {
+        return Ok(cell.output.clone());
+    }
+    Ok(cell
+        .output
+        .clone()
+        .as_builder()
+        .capacity(capacity.pack())
+        .build())
+}
+
+fn output_capacity(output: &CellOutput) -> u64 {
+    output.capacity().unpack()
+}
+
+// The output time info keeps the slot and the layout of the input, except that the legacy
+// timestamp is migrated to the versioned layout with the timestamp in milliseconds
+fn build_output_info(input_info: &TimeInfoData, time: TimeValue) -> Result<TimeInfoData, Error> {
+    let index = input_info.index();
+    Ok(match (input_info, time) {
+        (TimeInfoData::Timestamp { .. }, TimeValue::Timestamp(timestamp)) => {
+            TimeInfoData::Timestamp { index, timestamp }
+        }
+        (TimeInfoData::BlockNumber { .. }, TimeValue::BlockNumber(block_number)) => {
+            TimeInfoData::BlockNumber {
+                index,
+                block_number,
+            }
+        }
+        (TimeInfoData::Timestamp { .. }, TimeValue::TimestampMillis(time))
+        | (TimeInfoData::Versioned { .. }, TimeValue::TimestampMillis(time)) => {
+            TimeInfoData::Versioned {
+                index,
+                kind: TimeKind::Timestamp,
+                time,
+            }
+        }
+        (TimeInfoData::Versioned { .. }, TimeValue::BlockNumber(time)) => TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::BlockNumber,
+            time,
+        },
+        (TimeInfoData::Versioned { .. }, TimeValue::Epoch(time)) => TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::Epoch,
+            time,
+        },
+        _ => return Err(Error::Rule(RuleError::TimeInfoKindNotSame)),
+    })
+}
+
+/// The message which is signed by the operators of the multi-sig info type args:
+/// blake2b(out point of the time info input | output time info cell data)
+pub fn info_update_message(tx: &TransactionView) -> Option<[u8; 32]> {
+    let info_input = tx.inputs().get(INFO_POSITION)?;
+    let info_data = tx.outputs_data().get(INFO_POSITION)?;
+    Some(time_cell_types::info_update_message(
+        info_input.previous_output().as_slice(),
+        &info_data.raw_data(),
+    ))
+}
+
+/// Put the recoverable signatures of the operators into the input_type of the time info input
+/// witness, and the lock of the witness is kept for the signer of the lock
+pub fn set_operator_signatures(tx: &TransactionView, signatures: &[[u8; 65]]) -> TransactionView {
+    let mut witnesses: Vec<_> = tx.witnesses().into_iter().collect();
+    while witnesses.len() <= INFO_POSITION {
+        witnesses.push(Bytes::new().pack());
+    }
+    let witness_data = witnesses[INFO_POSITION].raw_data();
+    let witness_args = if witness_data.is_empty() {
+        WitnessArgs::default()
+    } else {
+        WitnessArgs::from_slice(&witness_data).unwrap_or_default()
+    };
+    let signatures: Vec<u8> = signatures
+        .iter()
+        .flat_map(|signature| signature.to_vec())
+        .collect();
+    witnesses[INFO_POSITION] = witness_args
+        .as_builder()
+        .input_type(Some(Bytes::from(signatures)).pack())
+        .build()
+        .as_bytes()
+        .pack();
+    tx.as_advanced_builder().set_witnesses(witnesses).build()
+}
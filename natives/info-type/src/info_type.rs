@@ -4,6 +4,8 @@ extern crate alloc;
 mod entry;
 #[path = "../../../contracts/info-type/src/error.rs"]
 mod error;
+#[path = "../../../contracts/info-type/src/secp256k1.rs"]
+mod secp256k1;
 
 fn main() {
     if let Err(err) = entry::main() {
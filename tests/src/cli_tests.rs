@@ -0,0 +1,271 @@
+use super::util::{
+    build_genesis_tx, commit_tx, create_funding_cell, deploy_oracle, Oracle, FEE,
+    GENESIS_TIMESTAMP, SUM_OF_TIME_INFO_CELLS,
+};
+use super::*;
+use ckb_testtool::context::Context;
+use ckb_time_cli::{
+    args::{index_state_args, index_state_type_id_args},
+    cell::{encode_index_state, encode_info, TimeCell},
+    report::{check_mock_tx, Action, Location, Violation},
+    Error as CliError,
+};
+use ckb_tool::ckb_types::{bytes::Bytes, core::TransactionView, packed::*, prelude::*};
+use time_cell_types::{
+    rules::RuleError, type_id, IndexStateData, IndexStateId, IndexStateTypeArgs, InfoTypeArgs,
+    InfoTypeCode, TimeInfoData, TimeKind, TimeValue, HEADER_DEP_PROOF_FLAG,
+};
+use time_oracle_sdk::{LiveCell, UpdateTx};
+
+fn new_update_tx(
+    context: &mut Context,
+    oracle: &Oracle,
+    index_state_cell: &LiveCell,
+    info_cell: &LiveCell,
+    latest_info_cell: &LiveCell,
+    time: TimeValue,
+) -> UpdateTx {
+    UpdateTx {
+        codes: oracle.codes.clone(),
+        lock_deps: vec![oracle.lock_script_dep.clone()],
+        index_state_cell: index_state_cell.clone(),
+        info_cell: info_cell.clone(),
+        latest_info_cell: latest_info_cell.clone(),
+        time,
+        header_dep: None,
+        extra_inputs: vec![create_funding_cell(context, oracle)],
+        change_lock: oracle.lock_script.clone(),
+        fee: FEE,
+    }
+}
+
+// The update of the slot 1 which is the output 2 of the genesis transaction
+fn build_update_tx(context: &mut Context, oracle: &Oracle) -> TransactionView {
+    let genesis_tx = build_genesis_tx(context, oracle, None);
+    let cells = commit_tx(context, &genesis_tx);
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    new_update_tx(context, oracle, &cells[0], &cells[2], &cells[1], time)
+        .build()
+        .expect("update tx")
+}
+
+fn replace_output_data(tx: &TransactionView, index: usize, data: Bytes) -> TransactionView {
+    let mut outputs_data: Vec<_> = tx.outputs_data().into_iter().collect();
+    outputs_data[index] = data.pack();
+    tx.as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build()
+}
+
+#[test]
+fn test_cli_decode_cell_data() {
+    let data = TimeInfoData::Timestamp {
+        index: 6,
+        timestamp: 1615365304,
+    }
+    .to_vec();
+    let cell = TimeCell::from_data(&data).expect("decode");
+    assert_eq!(
+        cell.to_string(),
+        "time info: version 0, index 6, timestamp 1615365304 s"
+    );
+
+    let data = IndexStateData::new_versioned(3, SUM_OF_TIME_INFO_CELLS).to_vec();
+    let cell = TimeCell::from_data(&data).expect("decode");
+    assert_eq!(
+        cell.to_string(),
+        "time index state: version 1, index 3, sum_of_time_info_cells 12"
+    );
+
+    assert!(TimeCell::from_data(&[0u8; 6]).is_err());
+}
+
+#[test]
+fn test_cli_encode_cell_data() {
+    let index_state = IndexStateData::new_with_updaters(2, SUM_OF_TIME_INFO_CELLS, vec![[1u8; 32]]);
+    let data = encode_index_state(&index_state).expect("encode");
+    assert_eq!(
+        TimeCell::from_data(&data),
+        Ok(TimeCell::IndexState(index_state))
+    );
+    assert!(matches!(
+        encode_index_state(&IndexStateData::new_versioned(12, SUM_OF_TIME_INFO_CELLS)),
+        Err(CliError::InvalidArgument(_))
+    ));
+
+    let data =
+        encode_info(1, TimeValue::TimestampMillis(GENESIS_TIMESTAMP), false).expect("encode");
+    assert_eq!(
+        TimeCell::from_data(&data),
+        Ok(TimeCell::Info(TimeInfoData::Versioned {
+            index: 1,
+            kind: TimeKind::Timestamp,
+            time: GENESIS_TIMESTAMP,
+        }))
+    );
+    let data = encode_info(1, TimeValue::BlockNumber(1000), true).expect("encode");
+    assert_eq!(
+        TimeCell::from_data(&data),
+        Ok(TimeCell::Info(TimeInfoData::BlockNumber {
+            index: 1,
+            block_number: 1000,
+        }))
+    );
+    assert!(matches!(
+        encode_info(1, TimeValue::TimestampMillis(GENESIS_TIMESTAMP), true),
+        Err(CliError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn test_cli_index_state_args() {
+    let out_point = OutPoint::new([7u8; 32].pack(), 1);
+    let args = index_state_args(&out_point, None, None);
+    assert_eq!(args.len(), 36);
+    assert_eq!(&args[..], out_point.as_slice());
+
+    let info_type_code = InfoTypeCode::new([8u8; 32], 1);
+    let owner_lock_hash = [9u8; 32];
+    let args = index_state_type_id_args(&out_point, 0, Some(info_type_code), Some(owner_lock_hash));
+    assert_eq!(args.len(), 97);
+    let first_input = CellInput::new(out_point, 0);
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&args),
+        Ok(IndexStateTypeArgs {
+            id: IndexStateId::TypeId(type_id(first_input.as_slice(), 0)),
+            info_type_code: Some(info_type_code),
+            owner_lock_hash: Some(owner_lock_hash),
+        })
+    );
+}
+
+#[test]
+fn test_cli_check_genesis_tx() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let tx = build_genesis_tx(&mut context, &oracle, None);
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+
+    // index state cell and time info cells of the outputs
+    let output_cells = report
+        .cells
+        .iter()
+        .filter(|cell| matches!(cell.location, Location::Output(_)))
+        .count();
+    assert_eq!(output_cells, SUM_OF_TIME_INFO_CELLS as usize + 1);
+    assert_eq!(report.verdicts.len(), 1);
+    assert_eq!(report.verdicts[0].action, Action::Genesis);
+    assert_eq!(report.verdicts[0].result, Ok(()));
+}
+
+#[test]
+fn test_cli_check_update_tx() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let tx = build_update_tx(&mut context, &oracle);
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+    assert_eq!(report.verdicts.len(), 1);
+    assert_eq!(report.verdicts[0].action, Action::Update);
+    assert_eq!(report.verdicts[0].result, Ok(()));
+
+    // The since of the time info input is not the updated time
+    let info_input = tx.inputs().get(1).expect("info input");
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(vec![
+            tx.inputs().get(0).expect("index state input"),
+            info_input.as_builder().since(0u64.pack()).build(),
+        ])
+        .build();
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+    assert_eq!(report.verdicts[0].result, Err(Violation::InvalidSince));
+
+    // The updaters of the time index state cell without an owner can't be changed
+    let tx = build_update_tx(&mut context, &oracle);
+    let index_state = IndexStateData::new_with_updaters(1, SUM_OF_TIME_INFO_CELLS, vec![[1u8; 32]]);
+    let tx = replace_output_data(&tx, 0, Bytes::from(index_state.to_vec()));
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+    assert_eq!(report.verdicts[0].result, Err(Violation::UpdatersChanged));
+}
+
+#[test]
+fn test_cli_check_genesis_tx_error() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let tx = build_genesis_tx(&mut context, &oracle, None);
+
+    // The ring of the time index state cell is empty
+    let empty_tx = replace_output_data(
+        &tx,
+        0,
+        Bytes::from(IndexStateData::new_versioned(0, 0).to_vec()),
+    );
+    let report = check_mock_tx(&build_mock_transaction(&empty_tx, &context));
+    assert_eq!(
+        report.verdicts[0].result,
+        Err(Violation::Rule(RuleError::TimeInfoAmountError))
+    );
+
+    // The time info cell of the slot 1 has other info type args of the same time index state
+    let info_output = tx.outputs().get(2).expect("info output");
+    let info_type = info_output.type_().to_opt().expect("info type");
+    let info_args: Bytes = info_type.args().unpack();
+    let mut other_info_args = InfoTypeArgs::from_slice(&info_args).expect("info type args");
+    other_info_args.flags = HEADER_DEP_PROOF_FLAG;
+    let other_info_type = info_type
+        .as_builder()
+        .args(Bytes::from(other_info_args.to_vec()).pack())
+        .build();
+    let mut outputs: Vec<_> = tx.outputs().into_iter().collect();
+    outputs[2] = info_output
+        .as_builder()
+        .type_(Some(other_info_type).pack())
+        .build();
+    let mixed_tx = tx.as_advanced_builder().set_outputs(outputs).build();
+    let report = check_mock_tx(&build_mock_transaction(&mixed_tx, &context));
+    assert_eq!(
+        report.verdicts[0].result,
+        Err(Violation::InfoTypeArgsNotSame)
+    );
+}
+
+#[test]
+fn test_cli_check_update_tx_not_bigger_than_latest() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let genesis_tx = build_genesis_tx(&mut context, &oracle, None);
+    let cells = commit_tx(&mut context, &genesis_tx);
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    let tx = new_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+        .build()
+        .expect("update tx");
+    let updated_cells = commit_tx(&mut context, &tx);
+
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 120_000);
+    let tx = new_update_tx(
+        &mut context,
+        &oracle,
+        &updated_cells[0],
+        &cells[3],
+        &updated_cells[1],
+        time,
+    )
+    .build()
+    .expect("update tx");
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+    assert_eq!(report.verdicts[0].result, Ok(()));
+
+    // The new time of the slot 2 is bigger than the overwritten time of the slot, but not the
+    // latest time of the slot 1 in the cell deps
+    let info_data = TimeInfoData::Versioned {
+        index: 2,
+        kind: TimeKind::Timestamp,
+        time: GENESIS_TIMESTAMP + 30_000,
+    };
+    let tx = replace_output_data(&tx, 1, Bytes::from(info_data.to_vec()));
+    let report = check_mock_tx(&build_mock_transaction(&tx, &context));
+    assert_eq!(
+        report.verdicts[0].result,
+        Err(Violation::Rule(RuleError::TimeNotBigger))
+    );
+}
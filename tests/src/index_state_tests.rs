@@ -4,43 +4,176 @@ use ckb_tool::ckb_error::assert_error_eq;
 use ckb_tool::ckb_script::ScriptError;
 use ckb_tool::ckb_types::{
     bytes::{BufMut, Bytes, BytesMut},
-    core::{TransactionBuilder, TransactionView},
+    core::{ScriptHashType, TransactionBuilder, TransactionView},
     packed::*,
     prelude::*,
 };
 use ckb_x64_simulator::RunningSetup;
 use std::collections::HashMap;
+use time_cell_types::{
+    type_id, IndexStateData, IndexStateTypeArgs, InfoTypeCode, INDEX_STATE_CELL_DATA_LEN,
+};
 
-const TIME_INDEX_CELL_DATA_LEN: usize = 2;
 const SUM_OF_TIME_INFO_CELLS: u8 = 12;
 const MAX_CYCLES: u64 = 10_000_000;
 
 // error numbers
 const INVALID_ARGUMENT: i8 = 5;
-const INDEX_STATE_TYPE_NOT_EXIST: i8 = 6;
 const INDEX_STATE_DATA_LEN_ERROR: i8 = 7;
 const TIME_INFO_AMOUNT_ERROR: i8 = 8;
 const TIME_INDEX_OUT_OF_BOUND: i8 = 9;
 const TIME_INDEX_INCREASE_ERROR: i8 = 10;
+const TIME_INFO_AMOUNT_NOT_SAME: i8 = 11;
+const INDEX_STATE_INPUTS_AMOUNT_ERROR: i8 = 12;
+const INDEX_STATE_OUTPUTS_AMOUNT_ERROR: i8 = 13;
+const INVALID_INDEX_STATE_VERSION: i8 = 14;
+const INDEX_STATE_VERSION_DOWNGRADE: i8 = 15;
+const UPDATER_NOT_AUTHORIZED: i8 = 16;
+const OWNER_NOT_AUTHORIZED: i8 = 17;
+const TIME_INFO_CELLS_NOT_ALL_BURNT: i8 = 18;
+const TIME_INDEX_CHANGED_IN_MIGRATION: i8 = 19;
+const TIME_INFO_CELLS_NOT_MIGRATED: i8 = 20;
+const TIME_INFO_NOT_UPDATED: i8 = 21;
+const TIME_INFO_INDEX_NOT_SAME: i8 = 22;
+const UPDATERS_CHANGED: i8 = 23;
+const INDEX_STATE_UPDATERS_EMPTY: i8 = 24;
+const TIME_INFO_CELLS_NOT_CREATED: i8 = 25;
 
 fn build_index_state_cell_data(index: u8, sum: u8) -> Bytes {
-    let mut time_buf = BytesMut::with_capacity(TIME_INDEX_CELL_DATA_LEN);
-    time_buf.put_u8(index);
-    time_buf.put_u8(sum);
-    Bytes::from(time_buf.to_vec())
+    Bytes::from(IndexStateData::new(index, sum).to_vec())
+}
+
+fn build_versioned_index_state_cell_data(index: u8, sum: u8) -> Bytes {
+    Bytes::from(IndexStateData::new_versioned(index, sum).to_vec())
+}
+
+fn build_index_state_cell_data_with_updaters(index: u8, updaters: Vec<[u8; 32]>) -> Bytes {
+    Bytes::from(IndexStateData::new_with_updaters(index, SUM_OF_TIME_INFO_CELLS, updaters).to_vec())
+}
+
+// The lock hash of the inputs which are built by the test contexts
+fn always_success_lock_hash() -> [u8; 32] {
+    let lock_script = Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS))
+        .hash_type(ScriptHashType::Data.into())
+        .build();
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(lock_script.calc_script_hash().as_slice());
+    lock_hash
 }
 
 fn build_invalid_index_state_cell_data() -> Bytes {
-    let mut time_buf = BytesMut::with_capacity(3);
-    for _ in 0..3 {
+    let mut time_buf = BytesMut::with_capacity(INDEX_STATE_CELL_DATA_LEN + 1);
+    for _ in 0..INDEX_STATE_CELL_DATA_LEN + 1 {
         time_buf.put_u8(0);
     }
     Bytes::from(time_buf.to_vec())
 }
 
+// The time index state cell can only be updated together with the time info cell of the new
+// index, so the always success time info cell with the index state type hash as the args is
+// consumed and created at the end of the transaction
+fn append_info_cells(
+    context: &mut Context,
+    tx: TransactionView,
+    index_state_output_index: usize,
+) -> TransactionView {
+    let index_state_output = tx
+        .outputs()
+        .get(index_state_output_index)
+        .expect("index state output");
+    let index_state_type_script = index_state_output
+        .type_()
+        .to_opt()
+        .expect("index state type script");
+    let index = tx
+        .outputs_data()
+        .get(index_state_output_index)
+        .and_then(|data| IndexStateData::from_slice(&data.raw_data()).ok())
+        .map(|index_state| index_state.index)
+        .unwrap_or_default();
+
+    let info_type_script = Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS))
+        .hash_type(ScriptHashType::Data.into())
+        .args(index_state_type_script.calc_script_hash().as_bytes().pack())
+        .build();
+    let info_output = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(index_state_output.lock())
+        .type_(Some(info_type_script).pack())
+        .build();
+    let info_input_out_point =
+        context.create_cell(info_output.clone(), Bytes::from(vec![index, 0, 0, 0, 0]));
+    tx.as_advanced_builder()
+        .input(
+            CellInput::new_builder()
+                .previous_output(info_input_out_point)
+                .build(),
+        )
+        .output(info_output)
+        .output_data(Bytes::from(vec![index, 0, 0, 0, 1]).pack())
+        .witness(Bytes::new().pack())
+        .build()
+}
+
+// The whole ring of the time info cells is created together with the time index state cell, so
+// the always success time info cells of all the slots with the index state type hash as the args
+// are created at the end of the transaction. No time info cell is created if the data of the time
+// index state cell can't be decoded.
+fn append_genesis_info_cells(
+    tx: TransactionView,
+    index_state_output_index: usize,
+) -> TransactionView {
+    let index_state_output = tx
+        .outputs()
+        .get(index_state_output_index)
+        .expect("index state output");
+    let index_state_type_script = index_state_output
+        .type_()
+        .to_opt()
+        .expect("index state type script");
+    let info_type_script = Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS))
+        .hash_type(ScriptHashType::Data.into())
+        .args(index_state_type_script.calc_script_hash().as_bytes().pack())
+        .build();
+    let info_output = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(index_state_output.lock())
+        .type_(Some(info_type_script).pack())
+        .build();
+    let sum = tx
+        .outputs_data()
+        .get(index_state_output_index)
+        .and_then(|data| IndexStateData::from_slice(&data.raw_data()).ok())
+        .map(|index_state| index_state.sum_of_time_info_cells)
+        .unwrap_or_default();
+    let mut builder = tx.as_advanced_builder();
+    for index in 0..sum {
+        builder = builder
+            .output(info_output.clone())
+            .output_data(Bytes::from(vec![index, 0, 0, 0, 0]).pack());
+    }
+    builder.build()
+}
+
+// Remove the time info cells which are appended by append_info_cells
+fn remove_info_cells(tx: TransactionView) -> TransactionView {
+    let inputs_count = tx.inputs().len() - 1;
+    let outputs_count = tx.outputs().len() - 1;
+    tx.as_advanced_builder()
+        .set_inputs(tx.inputs().into_iter().take(inputs_count).collect())
+        .set_outputs(tx.outputs().into_iter().take(outputs_count).collect())
+        .set_outputs_data(tx.outputs_data().into_iter().take(outputs_count).collect())
+        .set_witnesses(tx.witnesses().into_iter().take(inputs_count).collect())
+        .build()
+}
+
 fn create_test_context(
     outputs_data: &Vec<Bytes>,
     is_type_args_error: bool,
+    is_index_state_output_duplicated: bool,
 ) -> (Context, TransactionView) {
     // deploy contract
     let mut context = Context::default();
@@ -82,6 +215,11 @@ fn create_test_context(
     let input = CellInput::new_builder()
         .previous_output(input_out_point)
         .build();
+    let another_type_script_opt = if is_index_state_output_duplicated {
+        Some(index_state_type_script.clone())
+    } else {
+        None
+    };
     let outputs = vec![
         CellOutput::new_builder()
             .capacity(500u64.pack())
@@ -91,6 +229,7 @@ fn create_test_context(
         CellOutput::new_builder()
             .capacity(500u64.pack())
             .lock(lock_script)
+            .type_(another_type_script_opt.pack())
             .build(),
     ];
 
@@ -105,6 +244,7 @@ fn create_test_context(
         .cell_dep(index_state_type_script_dep)
         .witnesses(witnesses.pack())
         .build();
+    let tx = append_genesis_info_cells(tx, 0);
     (context, tx)
 }
 
@@ -112,6 +252,7 @@ fn create_test_context_with_index_state_inputs(
     input_data: Bytes,
     outputs_data: &Vec<Bytes>,
     type_of_cells_not_same: bool,
+    is_index_state_destroyed: bool,
 ) -> (Context, TransactionView) {
     // deploy contract
     let mut context = Context::default();
@@ -163,8 +304,10 @@ fn create_test_context_with_index_state_inputs(
             .build(),
     ];
 
-    let mut outputs = if type_of_cells_not_same {
-        let another_args = Bytes::copy_from_slice(index_state_input_out_point.as_slice().clone());
+    let mut outputs = if is_index_state_destroyed {
+        vec![]
+    } else if type_of_cells_not_same {
+        let another_args = index_state_input_out_point.tx_hash().as_bytes();
         let another_index_state_type_script = context
             .build_script(&index_state_out_point, another_args)
             .expect("script");
@@ -198,6 +341,111 @@ fn create_test_context_with_index_state_inputs(
         .cell_dep(index_state_type_script_dep)
         .witnesses(witnesses.pack())
         .build();
+    if is_index_state_destroyed || type_of_cells_not_same {
+        return (context, tx);
+    }
+    let tx = append_info_cells(&mut context, tx, 0);
+    (context, tx)
+}
+
+fn create_test_context_with_batch_index_state_cells(
+    input_data: Bytes,
+    outputs_data: &Vec<Bytes>,
+) -> (Context, TransactionView) {
+    // deploy contract
+    let mut context = Context::default();
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point)
+        .build();
+
+    let existing_normal_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let existing_index_state_type_script = context
+        .build_script(
+            &index_state_out_point,
+            Bytes::copy_from_slice(existing_normal_out_point.as_slice()),
+        )
+        .expect("script");
+
+    let index_state_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(existing_index_state_type_script.clone()).pack())
+            .build(),
+        input_data,
+    );
+
+    let normal_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let new_index_state_type_script = context
+        .build_script(
+            &index_state_out_point,
+            Bytes::copy_from_slice(normal_out_point.as_slice()),
+        )
+        .expect("script");
+    let index_state_type_script_dep = CellDep::new_builder()
+        .out_point(index_state_out_point)
+        .build();
+
+    let inputs = vec![
+        CellInput::new_builder()
+            .previous_output(index_state_input_out_point)
+            .build(),
+        CellInput::new_builder()
+            .previous_output(normal_out_point)
+            .build(),
+    ];
+    let outputs = vec![
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(existing_index_state_type_script).pack())
+            .build(),
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(new_index_state_type_script).pack())
+            .build(),
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script)
+            .build(),
+    ];
+
+    let witnesses = vec![Bytes::new(), Bytes::new()];
+
+    // build transaction
+    let tx = TransactionBuilder::default()
+        .inputs(inputs)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .cell_dep(lock_script_dep)
+        .cell_dep(index_state_type_script_dep)
+        .witnesses(witnesses.pack())
+        .build();
+    let tx = append_info_cells(&mut context, tx, 0);
+    let tx = append_genesis_info_cells(tx, 1);
     (context, tx)
 }
 
@@ -207,7 +455,7 @@ fn test_create_index_state_cells_success() {
         build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
         Bytes::new(),
     ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
     // run
@@ -240,7 +488,7 @@ fn test_update_index_state_cells_success() {
         Bytes::new(),
     ];
     let (mut context, tx) =
-        create_test_context_with_index_state_inputs(input_data, &outputs_data, false);
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
     // run
@@ -265,6 +513,82 @@ fn test_update_index_state_cells_success() {
     );
 }
 
+#[test]
+fn test_error_update_index_state_cells_without_info_cell() {
+    // Only the index is rotated, and the time info cell of the new index is not updated
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+    let tx = remove_info_cells(tx);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_NOT_UPDATED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_update_index_state_cells_with_two_info_cells() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+    let tx = append_info_cells(&mut context, tx, 0);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_NOT_UPDATED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_update_index_state_cells_info_index_not_same() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    // The created time info cell is of the slot 3 rather than the new index 2
+    let mut outputs_data: Vec<_> = tx.outputs_data().into_iter().collect();
+    outputs_data.pop();
+    outputs_data.push(Bytes::from(vec![3, 0, 0, 0, 1]).pack());
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs_data(outputs_data)
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_INDEX_NOT_SAME)
+            .input_type_script(script_cell_index)
+    );
+}
+
 #[test]
 fn test_update_full_index_state_cells_success() {
     let input_data = build_index_state_cell_data(11, SUM_OF_TIME_INFO_CELLS);
@@ -273,7 +597,7 @@ fn test_update_full_index_state_cells_success() {
         Bytes::new(),
     ];
     let (mut context, tx) =
-        create_test_context_with_index_state_inputs(input_data, &outputs_data, false);
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
     // run
@@ -299,9 +623,35 @@ fn test_update_full_index_state_cells_success() {
 }
 
 #[test]
-fn test_error_index_state_len() {
-    let outputs_data = vec![build_invalid_index_state_cell_data(), Bytes::new()];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+fn test_create_index_state_cells_with_custom_amount_success() {
+    for sum in [4u8, 64u8].iter() {
+        let outputs_data = vec![build_index_state_cell_data(0, *sum), Bytes::new()];
+        let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+        let tx = context.complete_tx(tx);
+        // run
+        let cycles = context
+            .verify_tx(&tx, MAX_CYCLES)
+            .expect("pass verification");
+        println!("consume cycles: {}", cycles);
+    }
+}
+
+#[test]
+fn test_error_create_index_state_cells_info_cell_missing() {
+    let outputs_data = vec![
+        build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    // The time info cell of the last slot is not created
+    let outputs_count = tx.outputs().len() - 1;
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs(tx.outputs().into_iter().take(outputs_count).collect())
+        .set_outputs_data(tx.outputs_data().into_iter().take(outputs_count).collect())
+        .build();
+
     let tx = context.complete_tx(tx);
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
@@ -309,9 +659,56 @@ fn test_error_index_state_len() {
     let script_cell_index = 0;
     assert_error_eq!(
         err,
-        ScriptError::ValidationFailure(INDEX_STATE_DATA_LEN_ERROR)
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_CREATED)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_create_index_state_cells_info_slot_duplicated() {
+    let outputs_data = vec![
+        build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    // The time info cell of the last slot takes the slot 0 instead
+    let outputs_count = tx.outputs().len() - 1;
+    let tx = tx
+        .as_advanced_builder()
+        .set_outputs_data(
+            tx.outputs_data()
+                .into_iter()
+                .take(outputs_count)
+                .chain(std::iter::once(Bytes::from(vec![0, 0, 0, 0, 0]).pack()))
+                .collect(),
+        )
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_CREATED)
             .output_type_script(script_cell_index)
     );
+}
+
+#[test]
+fn test_update_full_index_state_cells_with_custom_amount_success() {
+    let input_data = build_index_state_cell_data(3, 4);
+    let outputs_data = vec![build_index_state_cell_data(0, 4), Bytes::new()];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
 
     // dump raw test tx files
     let setup = RunningSetup {
@@ -321,7 +718,7 @@ fn test_error_index_state_len() {
         native_binaries: HashMap::default(),
     };
     write_native_setup(
-        "test_error_index_state_len",
+        "test_update_full_index_state_cells_with_custom_amount_success",
         "ckb-time-index-state-type-sim",
         &tx,
         &context,
@@ -330,29 +727,32 @@ fn test_error_index_state_len() {
 }
 
 #[test]
-fn test_error_info_amount() {
-    let outputs_data = vec![build_index_state_cell_data(0, 10), Bytes::new()];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+fn test_batch_create_and_update_index_state_cells_success() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        build_index_state_cell_data(0, 4),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_batch_index_state_cells(input_data, &outputs_data);
+
     let tx = context.complete_tx(tx);
     // run
-    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
-
-    let script_cell_index = 0;
-    assert_error_eq!(
-        err,
-        ScriptError::ValidationFailure(TIME_INFO_AMOUNT_ERROR)
-            .output_type_script(script_cell_index)
-    );
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
 
     // dump raw test tx files
     let setup = RunningSetup {
         is_lock_script: false,
         is_output: true,
-        script_index: 0,
+        script_index: 1,
         native_binaries: HashMap::default(),
     };
     write_native_setup(
-        "test_error_info_amount",
+        "test_batch_create_and_update_index_state_cells_success",
         "ckb-time-index-state-type-sim",
         &tx,
         &context,
@@ -361,32 +761,51 @@ fn test_error_info_amount() {
 }
 
 #[test]
-fn test_error_index_out_of_bound() {
+fn test_error_batch_create_index_state_cells_index_out_of_bound() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
     let outputs_data = vec![
-        build_index_state_cell_data(13, SUM_OF_TIME_INFO_CELLS),
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        build_index_state_cell_data(4, 4),
         Bytes::new(),
     ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+    let (mut context, tx) =
+        create_test_context_with_batch_index_state_cells(input_data, &outputs_data);
+
     let tx = context.complete_tx(tx);
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
-    let script_cell_index = 0;
+    let script_cell_index = 1;
     assert_error_eq!(
         err,
         ScriptError::ValidationFailure(TIME_INDEX_OUT_OF_BOUND)
             .output_type_script(script_cell_index)
     );
+}
+
+#[test]
+fn test_destroy_index_state_cells_success() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![Bytes::new()];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, true);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
 
     // dump raw test tx files
     let setup = RunningSetup {
         is_lock_script: false,
-        is_output: true,
+        is_output: false,
         script_index: 0,
         native_binaries: HashMap::default(),
     };
     write_native_setup(
-        "test_error_index_out_of_bound",
+        "test_destroy_index_state_cells_success",
         "ckb-time-index-state-type-sim",
         &tx,
         &context,
@@ -395,12 +814,12 @@ fn test_error_index_out_of_bound() {
 }
 
 #[test]
-fn test_error_args_invalid() {
+fn test_error_index_state_outputs_amount() {
     let outputs_data = vec![
         build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
-        Bytes::new(),
+        build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
     ];
-    let (mut context, tx) = create_test_context(&outputs_data, true);
+    let (mut context, tx) = create_test_context(&outputs_data, false, true);
     let tx = context.complete_tx(tx);
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
@@ -408,7 +827,176 @@ fn test_error_args_invalid() {
     let script_cell_index = 0;
     assert_error_eq!(
         err,
-        ScriptError::ValidationFailure(INVALID_ARGUMENT).output_type_script(script_cell_index)
+        ScriptError::ValidationFailure(INDEX_STATE_OUTPUTS_AMOUNT_ERROR)
+            .output_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_index_state_outputs_amount",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_index_state_inputs_amount() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context_with_index_state_inputs(
+        input_data.clone(),
+        &outputs_data,
+        false,
+        false,
+    );
+
+    // Another time index state cell with the same type script is consumed in the transaction
+    let index_state_output = tx.outputs().get(0).expect("index state output");
+    let another_index_state_out_point = context.create_cell(index_state_output, input_data);
+    let tx = tx
+        .as_advanced_builder()
+        .input(
+            CellInput::new_builder()
+                .previous_output(another_index_state_out_point)
+                .build(),
+        )
+        .witness(Bytes::new().pack())
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_INPUTS_AMOUNT_ERROR)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_index_state_len() {
+    let outputs_data = vec![build_invalid_index_state_cell_data(), Bytes::new()];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_DATA_LEN_ERROR)
+            .output_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_index_state_len",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_info_amount() {
+    let outputs_data = vec![build_index_state_cell_data(0, 0), Bytes::new()];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_AMOUNT_ERROR)
+            .output_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_info_amount",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_index_out_of_bound() {
+    let outputs_data = vec![
+        build_index_state_cell_data(13, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INDEX_OUT_OF_BOUND)
+            .output_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_index_out_of_bound",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_args_invalid() {
+    let outputs_data = vec![
+        build_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, true, false);
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).output_type_script(script_cell_index)
     );
 
     // dump raw test tx files
@@ -435,14 +1023,18 @@ fn test_error_type_of_cells_not_same() {
         Bytes::new(),
     ];
     let (mut context, tx) =
-        create_test_context_with_index_state_inputs(input_data, &outputs_data, true);
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, true, false);
 
     let tx = context.complete_tx(tx);
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
+    // The input time index state cell is destroyed and the output one is created with invalid args
     let script_cell_index = 0;
-    assert_type_script_error(err, INDEX_STATE_TYPE_NOT_EXIST, script_cell_index);
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).output_type_script(script_cell_index)
+    );
 }
 
 #[test]
@@ -453,7 +1045,7 @@ fn test_error_index_not_increase() {
         Bytes::new(),
     ];
     let (mut context, tx) =
-        create_test_context_with_index_state_inputs(input_data, &outputs_data, false);
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
     // run
@@ -481,3 +1073,812 @@ fn test_error_index_not_increase() {
         &setup,
     );
 }
+
+#[test]
+fn test_error_info_amount_not_same() {
+    let input_data = build_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS + 1),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_AMOUNT_NOT_SAME)
+            .input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_info_amount_not_same",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_create_versioned_index_state_cells_success() {
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_migrate_index_state_cells_to_versioned_success() {
+    let input_data = build_index_state_cell_data(11, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_migrate_index_state_cells_to_versioned_success",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_index_state_version_downgrade() {
+    let input_data = build_versioned_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data(2, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_VERSION_DOWNGRADE)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_index_state_version() {
+    let mut data = IndexStateData::new_versioned(0, SUM_OF_TIME_INFO_CELLS).to_vec();
+    data[0] = 3;
+    let outputs_data = vec![Bytes::from(data), Bytes::new()];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_INDEX_STATE_VERSION)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_create_index_state_cells_with_updaters_success() {
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(0, vec![[1u8; 32], [2u8; 32]]),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_update_index_state_cells_with_updaters_success() {
+    let updaters = vec![[1u8; 32], always_success_lock_hash()];
+    let input_data = build_index_state_cell_data_with_updaters(1, updaters.clone());
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(2, updaters),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_update_index_state_cells_with_updaters_success",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_updater_not_authorized() {
+    let input_data = build_index_state_cell_data_with_updaters(1, vec![[1u8; 32]]);
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(2, vec![[1u8; 32]]),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(UPDATER_NOT_AUTHORIZED).input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_updater_not_authorized",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_updaters_changed() {
+    // One of the updaters removes the others from the cell without an owner
+    let input_data =
+        build_index_state_cell_data_with_updaters(1, vec![[1u8; 32], always_success_lock_hash()]);
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(2, vec![always_success_lock_hash()]),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(UPDATERS_CHANGED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_updaters_added_without_owner() {
+    // Everyone can update the cell without updaters, but nobody can take it over
+    let input_data = build_versioned_index_state_cell_data(1, SUM_OF_TIME_INFO_CELLS);
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(2, vec![always_success_lock_hash()]),
+        Bytes::new(),
+    ];
+    let (mut context, tx) =
+        create_test_context_with_index_state_inputs(input_data, &outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(UPDATERS_CHANGED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_index_state_updaters_empty() {
+    let outputs_data = vec![
+        build_index_state_cell_data_with_updaters(0, vec![]),
+        Bytes::new(),
+    ];
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_UPDATERS_EMPTY)
+            .output_type_script(script_cell_index)
+    );
+}
+
+// The time index state cell with the owner is burnt together with info_cells_count time info
+// cells, whose type scripts are always success with the index state type hash as the args
+fn create_burn_test_context(
+    owner_lock_hash: [u8; 32],
+    info_cells_count: usize,
+) -> (Context, TransactionView) {
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    create_test_context_with_owner(owner_lock_hash, input_data, None, info_cells_count, 0)
+}
+
+// The time info cells of the tests are always success, so the code of always success is
+// committed as the info type code
+fn always_success_info_type_code() -> InfoTypeCode {
+    let mut code_hash = [0u8; 32];
+    code_hash.copy_from_slice(CellOutput::calc_data_hash(&ALWAYS_SUCCESS).as_slice());
+    InfoTypeCode::new(code_hash, ScriptHashType::Data as u8)
+}
+
+// The time index state cell with the owner is updated if output_data_opt exists, otherwise it's
+// burnt, and the time info cells are always success with the index state type hash as the args
+fn create_test_context_with_owner(
+    owner_lock_hash: [u8; 32],
+    input_data: Bytes,
+    output_data_opt: Option<Bytes>,
+    info_inputs_count: usize,
+    info_outputs_count: usize,
+) -> (Context, TransactionView) {
+    create_test_context_with_info_type_code(
+        always_success_info_type_code(),
+        owner_lock_hash,
+        input_data,
+        output_data_opt,
+        info_inputs_count,
+        info_outputs_count,
+    )
+}
+
+// The time info cells are counted only if their code is the info type code of the args
+fn create_test_context_with_info_type_code(
+    info_type_code: InfoTypeCode,
+    owner_lock_hash: [u8; 32],
+    input_data: Bytes,
+    output_data_opt: Option<Bytes>,
+    info_inputs_count: usize,
+    info_outputs_count: usize,
+) -> (Context, TransactionView) {
+    // deploy contract
+    let mut context = Context::default();
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point.clone())
+        .build();
+
+    let normal_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(50000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+
+    let mut out_point = [0u8; 36];
+    out_point.copy_from_slice(normal_out_point.as_slice());
+    let args =
+        IndexStateTypeArgs::new(out_point, Some(info_type_code), Some(owner_lock_hash)).to_vec();
+    let index_state_type_script = context
+        .build_script(&index_state_out_point, Bytes::from(args))
+        .expect("script");
+    let index_state_type_script_dep = CellDep::new_builder()
+        .out_point(index_state_out_point)
+        .build();
+    let info_type_script = context
+        .build_script(
+            &always_success_out_point,
+            index_state_type_script.calc_script_hash().as_bytes(),
+        )
+        .expect("script");
+
+    let index_state_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(index_state_type_script.clone()).pack())
+            .build(),
+        input_data,
+    );
+
+    let mut inputs = vec![CellInput::new_builder()
+        .previous_output(index_state_input_out_point)
+        .build()];
+    for index in 0..info_inputs_count {
+        let info_input_out_point = context.create_cell(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(info_type_script.clone()).pack())
+                .build(),
+            Bytes::from(vec![index as u8, 0, 0, 0, 0]),
+        );
+        inputs.push(
+            CellInput::new_builder()
+                .previous_output(info_input_out_point)
+                .build(),
+        );
+    }
+    inputs.push(
+        CellInput::new_builder()
+            .previous_output(normal_out_point)
+            .build(),
+    );
+
+    let mut outputs = vec![];
+    let mut outputs_data = vec![];
+    if let Some(output_data) = output_data_opt {
+        outputs.push(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(index_state_type_script).pack())
+                .build(),
+        );
+        outputs_data.push(output_data);
+    }
+    for index in 0..info_outputs_count {
+        outputs.push(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(info_type_script.clone()).pack())
+                .build(),
+        );
+        outputs_data.push(Bytes::from(vec![index as u8, 0, 0, 0, 0]));
+    }
+    // The capacity of the burnt cells is returned to the owner
+    let inputs_capacity = 50000u64 + 500u64 * (info_inputs_count as u64 + 1);
+    let change_capacity = inputs_capacity - 500u64 * outputs.len() as u64;
+    outputs.push(
+        CellOutput::new_builder()
+            .capacity(change_capacity.pack())
+            .lock(lock_script)
+            .build(),
+    );
+    outputs_data.push(Bytes::new());
+
+    let witnesses = vec![Bytes::new(); inputs.len()];
+
+    // build transaction
+    let tx = TransactionBuilder::default()
+        .inputs(inputs)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .cell_dep(lock_script_dep)
+        .cell_dep(index_state_type_script_dep)
+        .witnesses(witnesses.pack())
+        .build();
+    (context, tx)
+}
+
+#[test]
+fn test_burn_index_state_cells_with_owner_success() {
+    let (mut context, tx) =
+        create_burn_test_context(always_success_lock_hash(), SUM_OF_TIME_INFO_CELLS as usize);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_burn_index_state_cells_with_owner_success",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_burn_owner_not_authorized() {
+    let (mut context, tx) = create_burn_test_context([1u8; 32], SUM_OF_TIME_INFO_CELLS as usize);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OWNER_NOT_AUTHORIZED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_burn_time_info_cells_not_all_burnt() {
+    let (mut context, tx) = create_burn_test_context(
+        always_success_lock_hash(),
+        SUM_OF_TIME_INFO_CELLS as usize - 1,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_ALL_BURNT)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_burn_time_info_cells_of_other_code() {
+    // The always success cells with the args of the oracle are not the time info cells of the
+    // committed info type code
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let info_type_code = InfoTypeCode::new([1u8; 32], ScriptHashType::Type as u8);
+    let (mut context, tx) = create_test_context_with_info_type_code(
+        info_type_code,
+        always_success_lock_hash(),
+        input_data,
+        None,
+        SUM_OF_TIME_INFO_CELLS as usize,
+        0,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_ALL_BURNT)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_grow_index_state_cells_success() {
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let output_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS + 4);
+    let (mut context, tx) = create_test_context_with_owner(
+        always_success_lock_hash(),
+        input_data,
+        Some(output_data),
+        0,
+        4,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_grow_index_state_cells_success",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_shrink_index_state_cells_success() {
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let output_data = build_versioned_index_state_cell_data(3, 8);
+    let (mut context, tx) = create_test_context_with_owner(
+        always_success_lock_hash(),
+        input_data,
+        Some(output_data),
+        4,
+        0,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_migrate_owner_not_authorized() {
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let output_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS + 4);
+    let (mut context, tx) =
+        create_test_context_with_owner([1u8; 32], input_data, Some(output_data), 0, 4);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OWNER_NOT_AUTHORIZED).input_type_script(script_cell_index)
+    );
+}
+
+// The index moves from the end of the ring to the time info cell of the slot zero
+#[test]
+fn test_update_updaters_by_owner_success() {
+    let input_data = build_index_state_cell_data_with_updaters(11, vec![[1u8; 32]]);
+    let output_data =
+        build_index_state_cell_data_with_updaters(0, vec![[2u8; 32], always_success_lock_hash()]);
+    let (mut context, tx) = create_test_context_with_owner(
+        always_success_lock_hash(),
+        input_data,
+        Some(output_data),
+        1,
+        1,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_update_updaters_owner_not_authorized() {
+    let input_data =
+        build_index_state_cell_data_with_updaters(11, vec![always_success_lock_hash()]);
+    let output_data = build_index_state_cell_data_with_updaters(0, vec![[2u8; 32]]);
+    let (mut context, tx) =
+        create_test_context_with_owner([1u8; 32], input_data, Some(output_data), 1, 1);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OWNER_NOT_AUTHORIZED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_migrate_index_changed() {
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let output_data = build_versioned_index_state_cell_data(4, SUM_OF_TIME_INFO_CELLS + 4);
+    let (mut context, tx) = create_test_context_with_owner(
+        always_success_lock_hash(),
+        input_data,
+        Some(output_data),
+        0,
+        4,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INDEX_CHANGED_IN_MIGRATION)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_migrate_time_info_cells_not_migrated() {
+    // The removed time info cells of the shrunk ring are not burnt
+    let input_data = build_versioned_index_state_cell_data(3, SUM_OF_TIME_INFO_CELLS);
+    let output_data = build_versioned_index_state_cell_data(3, 8);
+    let (mut context, tx) = create_test_context_with_owner(
+        always_success_lock_hash(),
+        input_data,
+        Some(output_data),
+        3,
+        0,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_MIGRATED)
+            .input_type_script(script_cell_index)
+    );
+}
+
+// The args of the time index state type script is the Type ID derived from the first input and
+// the output index of zero, or the output index of one if is_type_id_error is true
+fn create_type_id_test_context(is_type_id_error: bool) -> (Context, TransactionView) {
+    // deploy contract
+    let mut context = Context::default();
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point)
+        .build();
+
+    // prepare cells
+    let input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let input = CellInput::new_builder()
+        .previous_output(input_out_point)
+        .build();
+
+    let output_index = if is_type_id_error { 1 } else { 0 };
+    let args = IndexStateTypeArgs::new_type_id(type_id(input.as_slice(), output_index), None, None);
+    let index_state_type_script = context
+        .build_script(&index_state_out_point, Bytes::from(args.to_vec()))
+        .expect("script");
+    let index_state_type_script_dep = CellDep::new_builder()
+        .out_point(index_state_out_point)
+        .build();
+
+    let outputs = vec![
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(index_state_type_script).pack())
+            .build(),
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script)
+            .build(),
+    ];
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(0, SUM_OF_TIME_INFO_CELLS),
+        Bytes::new(),
+    ];
+
+    let witnesses = vec![Bytes::new()];
+
+    // build transaction
+    let tx = TransactionBuilder::default()
+        .input(input)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .cell_dep(lock_script_dep)
+        .cell_dep(index_state_type_script_dep)
+        .witnesses(witnesses.pack())
+        .build();
+    let tx = append_genesis_info_cells(tx, 0);
+    (context, tx)
+}
+
+#[test]
+fn test_create_index_state_cells_with_type_id_success() {
+    let (mut context, tx) = create_type_id_test_context(false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_create_index_state_cells_with_type_id_success",
+        "ckb-time-index-state-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_type_id_invalid() {
+    let (mut context, tx) = create_type_id_test_context(true);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).output_type_script(script_cell_index)
+    );
+}
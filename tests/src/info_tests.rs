@@ -1,36 +1,75 @@
 use super::*;
 use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::ckb_crypto::secp::{Generator, Privkey};
 use ckb_tool::ckb_error::assert_error_eq;
 use ckb_tool::ckb_script::ScriptError;
 use ckb_tool::ckb_types::{
     bytes::{BufMut, Bytes, BytesMut},
-    core::{TransactionBuilder, TransactionView},
+    core::{HeaderBuilder, HeaderView, ScriptHashType, TransactionBuilder, TransactionView},
     packed::*,
     prelude::*,
+    H256,
 };
 
 use ckb_x64_simulator::RunningSetup;
 use std::collections::HashMap;
+use time_cell_types::{
+    blake160, epoch_with_fraction, info_update_message, IndexStateData, IndexStateTypeArgs,
+    InfoTypeArgs, InfoTypeCode, TimeInfoData, BLOCK_NUMBER_KIND, EPOCH_KIND, HEADER_DEP_PROOF_FLAG,
+    INDEX_STATE_CELL_DATA_LEN, INFO_DATA_VERSION, MULTI_SIG_FLAG, SINCE_EPOCH_BASE,
+    SINCE_TIMESTAMP_BASE, TIMESTAMP_KIND, VERSIONED_INFO_DATA_LEN,
+};
 
-const TIME_INDEX_CELL_DATA_LEN: usize = 2;
 const SUM_OF_TIME_INFO_CELLS: u8 = 12;
-const TIMESTAMP_DATA_LEN: usize = 5;
-const BLOCK_NUMBER_DATA_LEN: usize = 9;
 
 const MAX_CYCLES: u64 = 10_000_000;
+// The cycles limit of a transaction of CKB, and the update signed by M operators must be under it
+const MAX_TX_VERIFY_CYCLES: u64 = 70_000_000;
 
 // error numbers
 const INVALID_ARGUMENT: i8 = 5;
 const TIME_INFO_DATA_LEN_ERROR: i8 = 6;
 const INDEX_STATE_DATA_LEN_ERROR: i8 = 7;
-const TIME_INFO_TYPE_NOT_EXIST: i8 = 8;
-const TIME_INFO_INDEX_NOT_SAME: i8 = 9;
 const OUTPUT_TIMESTAMP_NOT_BIGGER: i8 = 10;
 const OUTPUT_BLOCK_NUMBER_NOT_BIGGER: i8 = 11;
 const INVALID_TIME_INFO_SINCE: i8 = 12;
+const INDEX_STATE_TYPE_NOT_EXIST: i8 = 13;
+const TIME_INFO_INDEX_OUT_OF_BOUND: i8 = 14;
+const INDEX_STATE_TYPE_DUPLICATED: i8 = 15;
+const TIME_INFO_INPUTS_AMOUNT_ERROR: i8 = 16;
+const TIME_INFO_OUTPUTS_AMOUNT_ERROR: i8 = 17;
+const INVALID_TIME_INFO_VERSION: i8 = 18;
+const TIME_INFO_KIND_NOT_SAME: i8 = 20;
+const OUTPUT_EPOCH_NOT_BIGGER: i8 = 21;
+const INVALID_TIME_INFO_EPOCH: i8 = 22;
+const HEADER_DEP_NOT_EXIST: i8 = 23;
+const TIME_INFO_NOT_SAME_AS_HEADER: i8 = 24;
+const TIME_INFO_STEP_TOO_SMALL: i8 = 25;
+const TIME_INFO_STEP_TOO_LARGE: i8 = 26;
+const TIME_INFO_VERSION_DOWNGRADE: i8 = 27;
+const SIGNATURES_NOT_EXIST: i8 = 29;
+const INVALID_SIGNATURE: i8 = 30;
+const SIGNATURES_NOT_ENOUGH: i8 = 31;
+const INDEX_STATE_NOT_BURNT: i8 = 32;
+const TIME_INFO_CELLS_NOT_ALL_BURNT: i8 = 33;
+const TIME_INFO_SLOT_DUPLICATED: i8 = 34;
+const TIME_INFO_SLOT_MISSING: i8 = 35;
+const TIME_INFO_SLOT_CHANGED: i8 = 36;
+const LATEST_TIME_INFO_NOT_EXIST: i8 = 38;
+const INDEX_STATE_OWNER_NOT_EXIST: i8 = 39;
+const INFO_TYPE_CODE_NOT_SAME: i8 = 40;
+const TIME_INFO_TYPE_ARGS_NOT_SAME: i8 = 41;
+
+// error numbers of the index state type script, which runs before the info type script when the
+// time index state cell is consumed
+const INDEX_STATE_TIME_INFO_NOT_UPDATED: i8 = 21;
+const INDEX_STATE_TIME_INFO_INDEX_NOT_SAME: i8 = 22;
+// error number of the index state type script, which checks the slots of the time info cells too
+// when the time index state cell is created
+const INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED: i8 = 25;
 
 fn build_index_state_cell_data(index: u8, is_data_len_err: bool) -> Bytes {
-    let mut time_buf = BytesMut::with_capacity(TIME_INDEX_CELL_DATA_LEN);
+    let mut time_buf = BytesMut::with_capacity(INDEX_STATE_CELL_DATA_LEN);
     time_buf.put_u8(index);
     if !is_data_len_err {
         time_buf.put_u8(SUM_OF_TIME_INFO_CELLS);
@@ -38,29 +77,108 @@ fn build_index_state_cell_data(index: u8, is_data_len_err: bool) -> Bytes {
     Bytes::from(time_buf.to_vec())
 }
 
+fn build_versioned_index_state_cell_data(index: u8) -> Bytes {
+    Bytes::from(IndexStateData::new_versioned(index, SUM_OF_TIME_INFO_CELLS).to_vec())
+}
+
 struct TimeData {
     timestamp: u32,
     block_number: u64,
 }
 fn build_time_info_cell_data(index: u8, time: TimeData) -> Bytes {
     if time.timestamp > 0 {
-        let mut time_buf = BytesMut::with_capacity(TIMESTAMP_DATA_LEN);
-        time_buf.put_u8(index);
-        time_buf.put_u32(time.timestamp);
-        Bytes::from(time_buf.to_vec())
+        Bytes::from(
+            TimeInfoData::Timestamp {
+                index,
+                timestamp: time.timestamp,
+            }
+            .to_vec(),
+        )
     } else if time.block_number > 0 {
-        let mut time_buf = BytesMut::with_capacity(BLOCK_NUMBER_DATA_LEN);
-        time_buf.put_u8(index);
-        time_buf.put_u64(time.block_number);
-        Bytes::from(time_buf.to_vec())
+        Bytes::from(
+            TimeInfoData::BlockNumber {
+                index,
+                block_number: time.block_number,
+            }
+            .to_vec(),
+        )
     } else {
         Bytes::new()
     }
 }
 
+fn build_versioned_time_info_cell_data(version: u8, index: u8, kind: u8, time: u64) -> Bytes {
+    let mut time_buf = BytesMut::with_capacity(VERSIONED_INFO_DATA_LEN);
+    time_buf.put_u8(version);
+    time_buf.put_u8(index);
+    time_buf.put_u8(kind);
+    time_buf.put_u64(time);
+    Bytes::from(time_buf.to_vec())
+}
+
+fn operator_pubkey_hash(privkey: &Privkey) -> [u8; 20] {
+    blake160(&privkey.pubkey().expect("pubkey").serialize())
+}
+
+// Put the signatures of the signers into the input_type of the time info input witness
+fn sign_info_tx(tx: TransactionView, signers: &[Privkey]) -> TransactionView {
+    let info_input_out_point = tx.inputs().get(1).expect("info input").previous_output();
+    let output_info_data = tx.outputs_data().get(1).expect("info output data");
+    let message = info_update_message(
+        info_input_out_point.as_slice(),
+        &output_info_data.raw_data(),
+    );
+    let mut signatures = Vec::new();
+    for signer in signers {
+        let signature = signer.sign_recoverable(&H256::from(message)).expect("sign");
+        signatures.extend_from_slice(&signature.serialize());
+    }
+    let witness_args = WitnessArgs::new_builder()
+        .input_type(Some(Bytes::from(signatures)).pack())
+        .build();
+    tx.as_advanced_builder()
+        .set_witnesses(vec![Bytes::new().pack(), witness_args.as_bytes().pack()])
+        .build()
+}
+
+fn build_header(number: u64, timestamp: u64, epoch: u64) -> HeaderView {
+    HeaderBuilder::default()
+        .number(number.pack())
+        .timestamp(timestamp.pack())
+        .epoch(epoch.pack())
+        .build()
+}
+
+// The genesis transaction creates the time index state cell and the time info cells, and the
+// time index state cell data is the first of the outputs_data, or the last if
+// is_index_state_output_last is true
 fn create_test_context(
     outputs_data: &Vec<Bytes>,
     is_type_args_error: bool,
+    is_index_state_output_last: bool,
+) -> (Context, TransactionView) {
+    create_test_context_with_info_type_code(
+        outputs_data,
+        is_type_args_error,
+        is_index_state_output_last,
+        None,
+    )
+}
+
+// The code of the info type script which is committed by the time index state type args
+fn build_info_type_code(info_bin: &Bytes) -> InfoTypeCode {
+    let mut code_hash = [0u8; 32];
+    code_hash.copy_from_slice(CellOutput::calc_data_hash(info_bin).as_slice());
+    InfoTypeCode::new(code_hash, ScriptHashType::Data as u8)
+}
+
+// The time index state type args commit the info type code if info_type_code_opt exists,
+// otherwise they are the legacy out point
+fn create_test_context_with_info_type_code(
+    outputs_data: &Vec<Bytes>,
+    is_type_args_error: bool,
+    is_index_state_output_last: bool,
+    info_type_code_opt: Option<InfoTypeCode>,
 ) -> (Context, TransactionView) {
     // deploy contract
     let mut context = Context::default();
@@ -82,19 +200,20 @@ fn create_test_context(
         .build();
 
     // prepare cells
+    let info_cells_count = outputs_data.len() - 1;
     let normal_input_out_point = context.create_cell(
         CellOutput::new_builder()
-            .capacity(500u64.pack())
+            .capacity((500u64 * outputs_data.len() as u64).pack())
             .lock(lock_script.clone())
             .build(),
         Bytes::new(),
     );
 
+    let mut out_point = [0u8; 36];
+    out_point.copy_from_slice(normal_input_out_point.as_slice());
+    let index_state_args = IndexStateTypeArgs::new(out_point, info_type_code_opt, None).to_vec();
     let index_state_type_script = context
-        .build_script(
-            &index_state_out_point,
-            Bytes::copy_from_slice(&normal_input_out_point.as_slice()),
-        )
+        .build_script(&index_state_out_point, Bytes::from(index_state_args))
         .expect("script");
     let index_state_type_script_dep = CellDep::new_builder()
         .out_point(index_state_out_point)
@@ -103,43 +222,38 @@ fn create_test_context(
     let args = if is_type_args_error {
         Bytes::new()
     } else {
-        Bytes::copy_from_slice(&normal_input_out_point.as_slice())
+        index_state_type_script.calc_script_hash().as_bytes()
     };
     let info_type_script = context.build_script(&info_out_point, args).expect("script");
     let info_type_script_dep = CellDep::new_builder().out_point(info_out_point).build();
 
-    let index_state_input_out_point = context.create_cell(
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script.clone())
-            .type_(Some(index_state_type_script.clone()).pack())
-            .build(),
-        build_index_state_cell_data(1, false),
-    );
-
-    let inputs = vec![
-        CellInput::new_builder()
-            .previous_output(normal_input_out_point)
-            .build(),
-        CellInput::new_builder()
-            .previous_output(index_state_input_out_point)
-            .build(),
-    ];
+    let inputs = vec![CellInput::new_builder()
+        .previous_output(normal_input_out_point)
+        .build()];
 
-    let outputs = vec![
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script.clone())
-            .type_(Some(index_state_type_script).pack())
-            .build(),
-        CellOutput::new_builder()
-            .capacity(500u64.pack())
-            .lock(lock_script)
-            .type_(Some(info_type_script).pack())
-            .build(),
-    ];
+    let index_state_output = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(lock_script.clone())
+        .type_(Some(index_state_type_script).pack())
+        .build();
+    let mut outputs = vec![];
+    if !is_index_state_output_last {
+        outputs.push(index_state_output.clone());
+    }
+    for _ in 0..info_cells_count {
+        outputs.push(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(info_type_script.clone()).pack())
+                .build(),
+        );
+    }
+    if is_index_state_output_last {
+        outputs.push(index_state_output);
+    }
 
-    let witnesses = vec![Bytes::new(), Bytes::new()];
+    let witnesses = vec![Bytes::new()];
 
     // build transaction
     let tx = TransactionBuilder::default()
@@ -154,11 +268,132 @@ fn create_test_context(
     (context, tx)
 }
 
+// The time index state cell data with the index of zero and the time info cell data of all the
+// slots which are built by build_info_data
+fn build_genesis_outputs_data<F>(build_info_data: F) -> Vec<Bytes>
+where
+    F: Fn(u8) -> Bytes,
+{
+    let mut outputs_data = vec![build_index_state_cell_data(0, false)];
+    outputs_data.extend((0..SUM_OF_TIME_INFO_CELLS).map(build_info_data));
+    outputs_data
+}
+
+// The time info cell data of the current index of the input time index state cell, which has
+// the same time as the input time info cell data, so the update is checked against the time of
+// the input time info cell unless the latest time info cell dep is replaced
+fn build_latest_info_cell_data(inputs_data: &[Bytes]) -> Bytes {
+    let index = match IndexStateData::from_slice(&inputs_data[0]) {
+        Ok(index_state_data) => index_state_data.index,
+        Err(_) => return inputs_data[1].clone(),
+    };
+    let latest_info = match TimeInfoData::from_slice(&inputs_data[1]) {
+        Ok(TimeInfoData::Timestamp { timestamp, .. }) => {
+            TimeInfoData::Timestamp { index, timestamp }
+        }
+        Ok(TimeInfoData::BlockNumber { block_number, .. }) => TimeInfoData::BlockNumber {
+            index,
+            block_number,
+        },
+        Ok(TimeInfoData::Versioned { kind, time, .. }) => {
+            TimeInfoData::Versioned { index, kind, time }
+        }
+        Err(_) => return inputs_data[1].clone(),
+    };
+    Bytes::from(latest_info.to_vec())
+}
+
+// Replace the latest time info cell dep, which is the last cell dep of the update transaction,
+// and the latest time info cell dep is removed if latest_info_data_opt is None
+fn set_latest_info_cell_dep(
+    context: &mut Context,
+    tx: TransactionView,
+    latest_info_data_opt: Option<Bytes>,
+) -> TransactionView {
+    let mut cell_deps: Vec<CellDep> = tx.cell_deps().into_iter().collect();
+    cell_deps.pop();
+    if let Some(latest_info_data) = latest_info_data_opt {
+        let info_output = tx.outputs().get(1).expect("info output");
+        let latest_info_out_point = context.create_cell(info_output, latest_info_data);
+        cell_deps.push(
+            CellDep::new_builder()
+                .out_point(latest_info_out_point)
+                .build(),
+        );
+    }
+    tx.as_advanced_builder().set_cell_deps(cell_deps).build()
+}
+
+// Replace the info type args of the update transaction with the legacy args, which are equal to
+// the out point args of the time index state type script
+fn set_legacy_info_type_args(
+    context: &mut Context,
+    tx: TransactionView,
+    inputs_data: &[Bytes],
+) -> TransactionView {
+    let index_state_type = tx
+        .outputs()
+        .get(0)
+        .and_then(|output| output.type_().to_opt())
+        .expect("index state type");
+    let info_output = tx.outputs().get(1).expect("info output");
+    let legacy_info_type = info_output
+        .type_()
+        .to_opt()
+        .expect("info type")
+        .as_builder()
+        .args(index_state_type.args())
+        .build();
+    let legacy_info_output = info_output
+        .as_builder()
+        .type_(Some(legacy_info_type).pack())
+        .build();
+    let info_input_out_point =
+        context.create_cell(legacy_info_output.clone(), inputs_data[1].clone());
+
+    let mut inputs: Vec<CellInput> = tx.inputs().into_iter().collect();
+    inputs[1] = inputs[1]
+        .clone()
+        .as_builder()
+        .previous_output(info_input_out_point)
+        .build();
+    let mut outputs: Vec<CellOutput> = tx.outputs().into_iter().collect();
+    outputs[1] = legacy_info_output;
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .set_outputs(outputs)
+        .build();
+    set_latest_info_cell_dep(context, tx, Some(build_latest_info_cell_data(inputs_data)))
+}
+
 fn create_test_context_with_info_inputs(
     inputs_data: &Vec<Bytes>,
     outputs_data: &Vec<Bytes>,
     since: u64,
     type_of_cells_not_same: bool,
+    header_opt: Option<HeaderView>,
+    steps_opt: Option<(u64, u64)>,
+) -> (Context, TransactionView) {
+    create_test_context_with_multi_sig_info_inputs(
+        inputs_data,
+        outputs_data,
+        since,
+        type_of_cells_not_same,
+        header_opt,
+        steps_opt,
+        None,
+    )
+}
+
+fn create_test_context_with_multi_sig_info_inputs(
+    inputs_data: &Vec<Bytes>,
+    outputs_data: &Vec<Bytes>,
+    since: u64,
+    type_of_cells_not_same: bool,
+    header_opt: Option<HeaderView>,
+    steps_opt: Option<(u64, u64)>,
+    multi_sig_opt: Option<(u8, Vec<[u8; 20]>)>,
 ) -> (Context, TransactionView) {
     // deploy contract
     let mut context = Context::default();
@@ -189,17 +424,49 @@ fn create_test_context_with_info_inputs(
 
     let args = Bytes::copy_from_slice(&normal_input_out_point.as_slice());
     let index_state_type_script = context
-        .build_script(&index_state_out_point, args.clone())
+        .build_script(&index_state_out_point, args)
         .expect("script");
     let index_state_type_script_dep = CellDep::new_builder()
         .out_point(index_state_out_point)
         .build();
 
-    let info_type_script = context.build_script(&info_out_point, args).expect("script");
+    // The header dep proof flag will be appended to the info type args if the header exists,
+    // and the min and max steps will be appended after the flags if they exist, and the
+    // threshold and operators will be appended after the steps if they exist
+    let mut index_state_type_hash = [0u8; 32];
+    index_state_type_hash.copy_from_slice(index_state_type_script.calc_script_hash().as_slice());
+    let mut info_args = InfoTypeArgs::new(index_state_type_hash);
+    if header_opt.is_some() {
+        info_args.flags |= HEADER_DEP_PROOF_FLAG;
+    }
+    if let Some((min_step, max_step)) = steps_opt {
+        info_args.min_step = min_step;
+        info_args.max_step = max_step;
+    }
+    if let Some((threshold, operators)) = multi_sig_opt {
+        info_args.flags |= MULTI_SIG_FLAG;
+        info_args.threshold = threshold;
+        info_args.operators = operators;
+    }
+    let info_type_script = context
+        .build_script(&info_out_point, Bytes::from(info_args.to_vec()))
+        .expect("script");
     let info_type_script_dep = CellDep::new_builder()
         .out_point(info_out_point.clone())
         .build();
 
+    // The info type script loads the secp256k1 library to verify the operator signatures
+    let secp256k1_lib_dep_opt = if info_args.has_multi_sig() {
+        let secp256k1_lib_out_point = context.deploy_cell(load_secp256k1_lib());
+        Some(
+            CellDep::new_builder()
+                .out_point(secp256k1_lib_out_point)
+                .build(),
+        )
+    } else {
+        None
+    };
+
     let index_state_input_out_point = context.create_cell(
         CellOutput::new_builder()
             .capacity(500u64.pack())
@@ -218,6 +485,19 @@ fn create_test_context_with_info_inputs(
         inputs_data[1].clone(),
     );
 
+    // The time info cell of the current index holds the latest time of the ring
+    let latest_info_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(info_type_script.clone()).pack())
+            .build(),
+        build_latest_info_cell_data(inputs_data),
+    );
+    let latest_info_dep = CellDep::new_builder()
+        .out_point(latest_info_out_point)
+        .build();
+
     let inputs = vec![
         CellInput::new_builder()
             .previous_output(index_state_input_out_point.clone())
@@ -235,7 +515,7 @@ fn create_test_context_with_info_inputs(
         .build()];
 
     if type_of_cells_not_same {
-        let another_args = Bytes::copy_from_slice(&index_state_input_out_point.as_slice());
+        let another_args = index_state_input_out_point.tx_hash().as_bytes();
         let another_info_type_script = context
             .build_script(&info_out_point, another_args)
             .expect("script");
@@ -259,31 +539,36 @@ fn create_test_context_with_info_inputs(
     let witnesses = vec![Bytes::new(), Bytes::new()];
 
     // build transaction
-    let tx = TransactionBuilder::default()
+    let mut tx_builder = TransactionBuilder::default()
         .inputs(inputs)
         .outputs(outputs)
         .outputs_data(outputs_data.pack())
         .cell_dep(lock_script_dep)
         .cell_dep(index_state_type_script_dep)
         .cell_dep(info_type_script_dep)
-        .witnesses(witnesses.pack())
-        .build();
+        .cell_deps(secp256k1_lib_dep_opt)
+        .cell_dep(latest_info_dep)
+        .witnesses(witnesses.pack());
+    if let Some(header) = header_opt {
+        tx_builder = tx_builder.header_dep(header.hash());
+        context.insert_header(header);
+    }
+    let tx = tx_builder.build();
     (context, tx)
 }
 
 #[test]
 fn test_create_info_timestamp_cells_success() {
-    let outputs_data = vec![
-        build_index_state_cell_data(2, false),
+    let outputs_data = build_genesis_outputs_data(|index| {
         build_time_info_cell_data(
-            2,
+            index,
             TimeData {
                 timestamp: 1614828683,
                 block_number: 0,
             },
-        ),
-    ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
     // run
@@ -295,17 +580,38 @@ fn test_create_info_timestamp_cells_success() {
 
 #[test]
 fn test_create_info_block_number_cells_success() {
-    let outputs_data = vec![
-        build_index_state_cell_data(2, false),
+    let outputs_data = build_genesis_outputs_data(|index| {
         build_time_info_cell_data(
-            2,
+            index,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
             },
-        ),
-    ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_create_info_cells_with_index_state_output_last_success() {
+    let mut outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            index,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        )
+    });
+    outputs_data.rotate_left(1);
+    let (mut context, tx) = create_test_context(&outputs_data, false, true);
 
     let tx = context.complete_tx(tx);
     // run
@@ -313,6 +619,21 @@ fn test_create_info_block_number_cells_success() {
         .verify_tx(&tx, MAX_CYCLES)
         .expect("pass verification");
     println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_create_info_cells_with_index_state_output_last_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
 }
 
 #[test]
@@ -320,7 +641,7 @@ fn test_update_info_timestamp_cells_success() {
     let inputs_data = vec![
         build_index_state_cell_data(6, false),
         build_time_info_cell_data(
-            6,
+            7,
             TimeData {
                 timestamp: 1614828683,
                 block_number: 0,
@@ -341,7 +662,7 @@ fn test_update_info_timestamp_cells_success() {
     let timestamp: u64 = 1614829080;
     let since = since_timestamp_base + timestamp;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
     // run
@@ -356,7 +677,7 @@ fn test_update_info_block_number_cells_success() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            0,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
@@ -375,7 +696,48 @@ fn test_update_info_block_number_cells_success() {
     ];
     let since: u64 = 10003;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+// The legacy oracle whose info type args are equal to the time index state type args
+fn build_legacy_update_data() -> (Vec<Bytes>, Vec<Bytes>, u64) {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614829080,
+                block_number: 0,
+            },
+        ),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    (inputs_data, outputs_data, since)
+}
+
+#[test]
+fn test_update_info_cells_with_legacy_args_success() {
+    let (inputs_data, outputs_data, since) = build_legacy_update_data();
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+    let tx = set_legacy_info_type_args(&mut context, tx, &inputs_data);
 
     let tx = context.complete_tx(tx);
     // run
@@ -385,42 +747,145 @@ fn test_update_info_block_number_cells_success() {
     println!("consume cycles: {}", cycles);
 }
 
+#[test]
+fn test_error_update_info_cells_with_legacy_args_index_state_duplicated() {
+    let (inputs_data, outputs_data, since) = build_legacy_update_data();
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+    let tx = set_legacy_info_type_args(&mut context, tx, &inputs_data);
+
+    // Another type script with the same args pretends to be the time index state cell
+    let index_state_output = tx.outputs().get(0).expect("index state output");
+    let index_state_type = index_state_output
+        .type_()
+        .to_opt()
+        .expect("index state type");
+    let fake_index_state_type = index_state_output
+        .lock()
+        .as_builder()
+        .args(index_state_type.args())
+        .build();
+    let fake_index_state_output = index_state_output
+        .as_builder()
+        .type_(Some(fake_index_state_type).pack())
+        .build();
+    let tx = tx
+        .as_advanced_builder()
+        .output(fake_index_state_output)
+        .output_data(build_index_state_cell_data(0, false).pack())
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_type_script_error(err, INDEX_STATE_TYPE_DUPLICATED, script_cell_index);
+}
+
 #[test]
 fn test_error_create_info_cells_invalid_args() {
-    let outputs_data = vec![
-        build_index_state_cell_data(2, false),
+    let outputs_data = build_genesis_outputs_data(|index| {
         build_time_info_cell_data(
-            2,
+            index,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
             },
-        ),
-    ];
-    let (mut context, tx) = create_test_context(&outputs_data, true);
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, true, false);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The time index state type script rejects the ring of the time info cells too
+    let script_cell_index = 1;
+    assert_any_type_script_error(
+        err,
+        &[
+            (INVALID_ARGUMENT, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
+    );
+}
+
+#[test]
+fn test_create_info_cells_with_info_type_code_success() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            index,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        )
+    });
+    let info_bin: Bytes = Loader::default().load_binary("info-type");
+    let (mut context, tx) = create_test_context_with_info_type_code(
+        &outputs_data,
+        false,
+        false,
+        Some(build_info_type_code(&info_bin)),
+    );
 
     let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_create_info_cells_info_type_code_not_same() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            index,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        )
+    });
+    // The code of always success is committed instead of the info type code
+    let (mut context, tx) = create_test_context_with_info_type_code(
+        &outputs_data,
+        false,
+        false,
+        Some(build_info_type_code(&ALWAYS_SUCCESS)),
+    );
 
+    let tx = context.complete_tx(tx);
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
+    // The time index state type script rejects the ring of the time info cells too
     let script_cell_index = 1;
-    assert_type_script_error(err, INVALID_ARGUMENT, script_cell_index);
+    assert_any_type_script_error(
+        err,
+        &[
+            (INFO_TYPE_CODE_NOT_SAME, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
+    );
 }
 
 #[test]
 fn test_error_create_info_cell_data_len() {
-    let outputs_data = vec![
-        build_index_state_cell_data(2, false),
+    let outputs_data = build_genesis_outputs_data(|index| {
+        let block_number = if index == 2 { 0 } else { 10000 };
         build_time_info_cell_data(
-            2,
+            index,
             TimeData {
                 timestamp: 0,
-                block_number: 0,
+                block_number,
             },
-        ),
-    ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
 
@@ -451,38 +916,128 @@ fn test_error_create_info_cell_data_len() {
 }
 
 #[test]
-fn test_error_index_state_cell_data_len() {
-    let outputs_data = vec![
-        build_index_state_cell_data(2, true),
+fn test_error_create_info_cell_index_out_of_bound() {
+    let outputs_data = build_genesis_outputs_data(|index| {
         build_time_info_cell_data(
-            2,
+            index + 1,
             TimeData {
                 timestamp: 0,
-                block_number: 1000,
+                block_number: 10000,
             },
-        ),
-    ];
-    let (mut context, tx) = create_test_context(&outputs_data, false);
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
 
     let tx = context.complete_tx(tx);
 
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
+    // The time index state type script rejects the ring of the time info cells too
     let script_cell_index = 1;
-    assert_error_eq!(
+    assert_any_type_script_error(
         err,
-        ScriptError::ValidationFailure(INDEX_STATE_DATA_LEN_ERROR)
-            .input_type_script(script_cell_index)
+        &[
+            (TIME_INFO_INDEX_OUT_OF_BOUND, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
     );
 
     // dump raw test tx files
     let setup = RunningSetup {
         is_lock_script: false,
-        is_output: false,
+        is_output: true,
         script_index: 1,
         native_binaries: HashMap::default(),
     };
+    write_native_setup(
+        "test_error_create_info_cell_index_out_of_bound",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_create_info_cells_slot_duplicated() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            if index == 3 { 2 } else { index },
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The time index state type script rejects the ring of the time info cells too
+    let script_cell_index = 1;
+    assert_any_type_script_error(
+        err,
+        &[
+            (TIME_INFO_SLOT_DUPLICATED, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_create_info_cells_slot_duplicated",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_index_state_cell_data_len() {
+    // The time info cells are checked before the time index state cell
+    let mut outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            index,
+            TimeData {
+                timestamp: 0,
+                block_number: 1000,
+            },
+        )
+    });
+    outputs_data[0] = build_index_state_cell_data(0, true);
+    outputs_data.rotate_left(1);
+    let (mut context, tx) = create_test_context(&outputs_data, false, true);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_DATA_LEN_ERROR)
+            .output_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 0,
+        native_binaries: HashMap::default(),
+    };
     write_native_setup(
         "test_error_index_state_cell_data_len",
         "ckb-time-info-type-sim",
@@ -492,6 +1047,101 @@ fn test_error_index_state_cell_data_len() {
     );
 }
 
+#[test]
+fn test_error_create_info_cells_index_state_duplicated() {
+    // The time info cells are checked before the time index state cells
+    let mut outputs_data = build_genesis_outputs_data(|index| {
+        build_time_info_cell_data(
+            index,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        )
+    });
+    outputs_data.rotate_left(1);
+    let (mut context, tx) = create_test_context(&outputs_data, false, true);
+
+    // Another time index state cell with the same type script is appended to the outputs, and
+    // its capacity is paid by an extra input
+    let index_state_output = tx.outputs().get(tx.outputs().len() - 1).expect("output");
+    let index_state_output_data = tx
+        .outputs_data()
+        .get(tx.outputs_data().len() - 1)
+        .expect("output data");
+    let extra_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(index_state_output.lock())
+            .build(),
+        Bytes::new(),
+    );
+    let tx = tx
+        .as_advanced_builder()
+        .input(
+            CellInput::new_builder()
+                .previous_output(extra_input_out_point)
+                .build(),
+        )
+        .witness(Bytes::new().pack())
+        .output(index_state_output)
+        .output_data(index_state_output_data)
+        .build();
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TYPE_DUPLICATED)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_info_type_not_same() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let since: u64 = 10003;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, true, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The output time info cell of another index state type hash is not the update of the time
+    // info cell, so the index state type script rejects the rotation of the index
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TIME_INFO_NOT_UPDATED)
+            .input_type_script(script_cell_index)
+    );
+}
+
 #[test]
 fn test_error_info_type_not_exist() {
     let inputs_data = vec![
@@ -516,15 +1166,25 @@ fn test_error_info_type_not_exist() {
     ];
     let since: u64 = 10003;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, true);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, true, None, None);
 
     let tx = context.complete_tx(tx);
 
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
+    // The script group of the input time info cell has no output, so it's checked as the burn of
+    // the time info cells with the time index state cell kept. The output time info cell of
+    // another index state type hash and the rotation of the index are rejected too.
     let script_cell_index = 1;
-    assert_type_script_error(err, TIME_INFO_TYPE_NOT_EXIST, script_cell_index);
+    assert_any_type_script_error(
+        err,
+        &[
+            (INDEX_STATE_NOT_BURNT, script_cell_index),
+            (INDEX_STATE_TYPE_NOT_EXIST, script_cell_index),
+            (INDEX_STATE_TIME_INFO_NOT_UPDATED, 0),
+        ],
+    );
 }
 
 #[test]
@@ -532,7 +1192,7 @@ fn test_error_info_index_not_same() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            1,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
@@ -551,15 +1211,20 @@ fn test_error_info_index_not_same() {
     ];
     let since: u64 = 10003;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
 
     // run
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
 
-    let script_cell_index = 1;
-    assert_type_script_error(err, TIME_INFO_INDEX_NOT_SAME, script_cell_index);
+    // The index state type script runs first and compares the index of the output time info
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TIME_INFO_INDEX_NOT_SAME)
+            .input_type_script(script_cell_index)
+    );
 }
 
 #[test]
@@ -567,7 +1232,7 @@ fn test_error_output_block_number_not_bigger() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            0,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
@@ -586,7 +1251,7 @@ fn test_error_output_block_number_not_bigger() {
     ];
     let since: u64 = 999;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
 
@@ -621,7 +1286,7 @@ fn test_error_output_block_number_since() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            0,
             TimeData {
                 timestamp: 0,
                 block_number: 10000,
@@ -640,7 +1305,7 @@ fn test_error_output_block_number_since() {
     ];
     let since: u64 = 10030;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
 
@@ -675,7 +1340,7 @@ fn test_error_output_timestamp_not_bigger() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            0,
             TimeData {
                 timestamp: 1614829080,
                 block_number: 0,
@@ -694,7 +1359,7 @@ fn test_error_output_timestamp_not_bigger() {
     ];
     let since: u64 = 1614829080;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
 
@@ -729,7 +1394,7 @@ fn test_error_output_timestamp_since() {
     let inputs_data = vec![
         build_index_state_cell_data(11, false),
         build_time_info_cell_data(
-            11,
+            0,
             TimeData {
                 timestamp: 1614829080,
                 block_number: 0,
@@ -748,7 +1413,7 @@ fn test_error_output_timestamp_since() {
     ];
     let since: u64 = 1614829580;
     let (mut context, tx) =
-        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false);
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
 
     let tx = context.complete_tx(tx);
 
@@ -777,3 +1442,1826 @@ fn test_error_output_timestamp_since() {
         &setup,
     );
 }
+
+#[test]
+fn test_error_output_timestamp_not_bigger_than_latest() {
+    // The new time is bigger than the overwritten time of slot 7, but not the latest time of
+    // slot 6
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+    let latest_info_data =
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 6, TIMESTAMP_KIND, 1614829090000);
+    let tx = set_latest_info_cell_dep(&mut context, tx, Some(latest_info_data));
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OUTPUT_TIMESTAMP_NOT_BIGGER)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_output_block_number_not_bigger_than_latest() {
+    // The new block number is bigger than the overwritten one of slot 0, but not the latest one
+    // of slot 11
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let since: u64 = 10003;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+    let latest_info_data = build_time_info_cell_data(
+        11,
+        TimeData {
+            timestamp: 0,
+            block_number: 10005,
+        },
+    );
+    let tx = set_latest_info_cell_dep(&mut context, tx, Some(latest_info_data));
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OUTPUT_BLOCK_NUMBER_NOT_BIGGER)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_latest_time_info_not_exist() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+    let tx = set_latest_info_cell_dep(&mut context, tx, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(LATEST_TIME_INFO_NOT_EXIST)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_create_info_versioned_timestamp_cells_success() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, index, TIMESTAMP_KIND, 1614828683123)
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_update_info_versioned_timestamp_cells_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_update_info_versioned_timestamp_cells_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_output_versioned_timestamp_since() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080456;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_TIME_INFO_SINCE)
+            .input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_output_versioned_timestamp_since",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_info_kind_not_same() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, BLOCK_NUMBER_KIND, 10000),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_KIND_NOT_SAME)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_create_info_cell_version() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION + 1,
+            index,
+            TIMESTAMP_KIND,
+            1614828683123,
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_TIME_INFO_VERSION)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_update_info_epoch_cells_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            0,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 900, 1800),
+        ),
+    ];
+    let output_epoch = epoch_with_fraction(1000, 1000, 1799);
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 0, EPOCH_KIND, output_epoch),
+    ];
+    let since = SINCE_EPOCH_BASE + output_epoch;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_update_info_epoch_cells_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_output_epoch_not_bigger() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            0,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 900, 1800),
+        ),
+    ];
+    let output_epoch = epoch_with_fraction(1000, 899, 1798);
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 0, EPOCH_KIND, output_epoch),
+    ];
+    let since = SINCE_EPOCH_BASE + output_epoch;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OUTPUT_EPOCH_NOT_BIGGER)
+            .input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_output_epoch_not_bigger",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_output_epoch_since() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            0,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 900, 1800),
+        ),
+    ];
+    let output_epoch = epoch_with_fraction(1001, 0, 1800);
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 0, EPOCH_KIND, output_epoch),
+    ];
+    let since = SINCE_EPOCH_BASE + epoch_with_fraction(1001, 1, 1800);
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_TIME_INFO_SINCE)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_create_info_cell_epoch() {
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            index,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 1800, 1800),
+        )
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_TIME_INFO_EPOCH)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_update_info_timestamp_cells_with_header_dep_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let header = build_header(10003, 1614829080456, epoch_with_fraction(1000, 1, 1800));
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        0,
+        false,
+        Some(header),
+        None,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_update_info_block_number_cells_with_header_dep_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let header = build_header(10003, 1614829080456, epoch_with_fraction(1000, 1, 1800));
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        0,
+        false,
+        Some(header),
+        None,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_info_not_same_as_header() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            0,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 900, 1800),
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            0,
+            EPOCH_KIND,
+            epoch_with_fraction(1000, 902, 1800),
+        ),
+    ];
+    let header = build_header(10003, 1614829080456, epoch_with_fraction(1000, 901, 1800));
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        0,
+        false,
+        Some(header),
+        None,
+    );
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_NOT_SAME_AS_HEADER)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_info_header_dep_not_exist() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let header = build_header(10003, 1614829080456, epoch_with_fraction(1000, 1, 1800));
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        0,
+        false,
+        Some(header),
+        None,
+    );
+    // The info type args require the header dep proof, but the header dep is removed
+    let tx = tx.as_advanced_builder().set_header_deps(vec![]).build();
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(HEADER_DEP_NOT_EXIST).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_update_info_block_number_cells_with_steps_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let since: u64 = 10003;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((1, 10)),
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_info_step_too_small() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let since: u64 = 10003;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((5, 10)),
+    );
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_STEP_TOO_SMALL)
+            .input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_info_step_too_small",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_info_step_too_large() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10003,
+            },
+        ),
+    ];
+    let since: u64 = 10003;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((1, 2)),
+    );
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_STEP_TOO_LARGE)
+            .input_type_script(script_cell_index)
+    );
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_error_info_step_too_large",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_info_step_from_latest_too_small() {
+    // The step from the overwritten block number of slot 0 is big enough, but not the step from
+    // the latest one of slot 11
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10010,
+            },
+        ),
+    ];
+    let since: u64 = 10010;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((5, 20)),
+    );
+    let latest_info_data = build_time_info_cell_data(
+        11,
+        TimeData {
+            timestamp: 0,
+            block_number: 10008,
+        },
+    );
+    let tx = set_latest_info_cell_dep(&mut context, tx, Some(latest_info_data));
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_STEP_TOO_SMALL)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_update_info_cells_with_latest_info_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(11, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10000,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(0, false),
+        build_time_info_cell_data(
+            0,
+            TimeData {
+                timestamp: 0,
+                block_number: 10016,
+            },
+        ),
+    ];
+    let since: u64 = 10016;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((5, 10)),
+    );
+    // The step from the overwritten block number is too large, but the step from the latest one
+    // is in the range
+    let latest_info_data = build_time_info_cell_data(
+        11,
+        TimeData {
+            timestamp: 0,
+            block_number: 10008,
+        },
+    );
+    let tx = set_latest_info_cell_dep(&mut context, tx, Some(latest_info_data));
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_migrate_info_timestamp_cells_to_versioned_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(7),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_migrate_info_timestamp_cells_to_versioned_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+// The steps of the info type args are always in milliseconds for the timestamp, so the same args
+// work before and after the migration of the time info cells
+#[test]
+fn test_update_legacy_info_timestamp_cells_with_millis_steps_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828743,
+                block_number: 0,
+            },
+        ),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614828743;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((60_000, 120_000)),
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_migrate_info_timestamp_cells_with_steps_success() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(7),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828743000),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614828743;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((60_000, 120_000)),
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_migrate_info_timestamp_cells_step_too_large() {
+    // The max step of 60 is 60 milliseconds rather than 60 seconds
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614828683,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_versioned_index_state_cell_data(7),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828743000),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614828743;
+    let (mut context, tx) = create_test_context_with_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        Some((1, 60)),
+    );
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_STEP_TOO_LARGE)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_migrate_info_timestamp_not_bigger() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614829080,
+                block_number: 0,
+            },
+        ),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080000),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OUTPUT_TIMESTAMP_NOT_BIGGER)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_info_version_downgrade() {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_time_info_cell_data(
+            7,
+            TimeData {
+                timestamp: 1614829080,
+                block_number: 0,
+            },
+        ),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_VERSION_DOWNGRADE)
+            .input_type_script(script_cell_index)
+    );
+}
+
+fn create_multi_sig_test_context(
+    operators: &[Privkey],
+    threshold: u8,
+) -> (Context, TransactionView) {
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let operator_pubkey_hashes = operators.iter().map(operator_pubkey_hash).collect();
+    create_test_context_with_multi_sig_info_inputs(
+        &inputs_data,
+        &outputs_data,
+        since,
+        false,
+        None,
+        None,
+        Some((threshold, operator_pubkey_hashes)),
+    )
+}
+
+fn generate_operators(count: usize) -> Vec<Privkey> {
+    let generator = Generator::new();
+    (0..count).map(|_| generator.gen_privkey()).collect()
+}
+
+#[test]
+fn test_update_info_cells_with_operator_signatures_success() {
+    let operators = generate_operators(3);
+    let (mut context, tx) = create_multi_sig_test_context(&operators, 2);
+
+    let tx = context.complete_tx(tx);
+    let tx = sign_info_tx(tx, &[operators[2].clone(), operators[0].clone()]);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_TX_VERIFY_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_operator_signatures_cycles() {
+    // The updates signed by M = 2 and M = 3 operators stay under the cycles limit of a transaction
+    let operators = generate_operators(3);
+    for threshold in 2..=operators.len() {
+        let (mut context, tx) = create_multi_sig_test_context(&operators, threshold as u8);
+
+        let tx = context.complete_tx(tx);
+        let tx = sign_info_tx(tx, &operators[..threshold]);
+        // run
+        let cycles = context
+            .verify_tx(&tx, MAX_TX_VERIFY_CYCLES)
+            .expect("pass verification");
+        assert!(cycles < MAX_TX_VERIFY_CYCLES);
+        println!("{} signatures consume cycles: {}", threshold, cycles);
+    }
+}
+
+#[test]
+fn test_error_operator_signatures_not_exist() {
+    let operators = generate_operators(3);
+    let (mut context, tx) = create_multi_sig_test_context(&operators, 2);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_TX_VERIFY_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(SIGNATURES_NOT_EXIST).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_signature_not_from_operator() {
+    let operators = generate_operators(3);
+    let (mut context, tx) = create_multi_sig_test_context(&operators, 2);
+
+    let tx = context.complete_tx(tx);
+    let another_signer = generate_operators(1).remove(0);
+    let tx = sign_info_tx(tx, &[operators[0].clone(), another_signer]);
+    // run
+    let err = context.verify_tx(&tx, MAX_TX_VERIFY_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_SIGNATURE).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_duplicated_operator_signatures() {
+    let operators = generate_operators(3);
+    let (mut context, tx) = create_multi_sig_test_context(&operators, 2);
+
+    let tx = context.complete_tx(tx);
+    let tx = sign_info_tx(tx, &[operators[1].clone(), operators[1].clone()]);
+    // run
+    let err = context.verify_tx(&tx, MAX_TX_VERIFY_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_SIGNATURE).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_operator_signatures_not_enough() {
+    let operators = generate_operators(3);
+    let (mut context, tx) = create_multi_sig_test_context(&operators, 2);
+
+    let tx = context.complete_tx(tx);
+    let tx = sign_info_tx(tx, &[operators[1].clone()]);
+    // run
+    let err = context.verify_tx(&tx, MAX_TX_VERIFY_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(SIGNATURES_NOT_ENOUGH).input_type_script(script_cell_index)
+    );
+}
+
+// The time index state cell and info_cells_count time info cells are burnt, and the time index
+// state cell is kept in the outputs with the next index if is_index_state_kept is true
+fn create_burn_test_context(
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    info_cells_count: usize,
+    is_index_state_kept: bool,
+) -> (Context, TransactionView) {
+    let output_data_opt = if is_index_state_kept {
+        Some(build_versioned_index_state_cell_data(4))
+    } else {
+        None
+    };
+    let info_inputs_indexes: Vec<u8> = (0..info_cells_count as u8).collect();
+    create_test_context_with_info_cells(
+        owner_lock_hash_opt,
+        build_versioned_index_state_cell_data(3),
+        output_data_opt,
+        &info_inputs_indexes,
+        &[],
+    )
+}
+
+// The time index state cell is updated if output_data_opt exists, otherwise it's burnt, and the
+// time info cells with the indexes are consumed and created in the same transaction
+fn create_test_context_with_info_cells(
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    input_data: Bytes,
+    output_data_opt: Option<Bytes>,
+    info_inputs_indexes: &[u8],
+    info_outputs_indexes: &[u8],
+) -> (Context, TransactionView) {
+    // deploy contract
+    let mut context = Context::default();
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    let info_bin: Bytes = Loader::default().load_binary("info-type");
+    let info_type_code = build_info_type_code(&info_bin);
+    let info_out_point = context.deploy_cell(info_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point)
+        .build();
+
+    let normal_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(50000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+
+    let mut out_point = [0u8; 36];
+    out_point.copy_from_slice(normal_input_out_point.as_slice());
+    let args =
+        IndexStateTypeArgs::new(out_point, Some(info_type_code), owner_lock_hash_opt).to_vec();
+    let index_state_type_script = context
+        .build_script(&index_state_out_point, Bytes::from(args))
+        .expect("script");
+    let index_state_type_script_dep = CellDep::new_builder()
+        .out_point(index_state_out_point)
+        .build();
+
+    let mut index_state_type_hash = [0u8; 32];
+    index_state_type_hash.copy_from_slice(index_state_type_script.calc_script_hash().as_slice());
+    let info_args = Bytes::from(InfoTypeArgs::new(index_state_type_hash).to_vec());
+    let info_type_script = context
+        .build_script(&info_out_point, info_args)
+        .expect("script");
+    let info_type_script_dep = CellDep::new_builder().out_point(info_out_point).build();
+
+    let index_state_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(index_state_type_script.clone()).pack())
+            .build(),
+        input_data,
+    );
+
+    let mut inputs = vec![CellInput::new_builder()
+        .previous_output(index_state_input_out_point)
+        .build()];
+    for index in info_inputs_indexes {
+        let info_input_out_point = context.create_cell(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(info_type_script.clone()).pack())
+                .build(),
+            build_versioned_time_info_cell_data(
+                INFO_DATA_VERSION,
+                *index,
+                TIMESTAMP_KIND,
+                1614828683123,
+            ),
+        );
+        inputs.push(
+            CellInput::new_builder()
+                .previous_output(info_input_out_point)
+                .build(),
+        );
+    }
+    inputs.push(
+        CellInput::new_builder()
+            .previous_output(normal_input_out_point)
+            .build(),
+    );
+
+    let mut outputs = vec![];
+    let mut outputs_data = vec![];
+    if let Some(output_data) = output_data_opt {
+        outputs.push(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(index_state_type_script).pack())
+                .build(),
+        );
+        outputs_data.push(output_data);
+    }
+    for index in info_outputs_indexes {
+        outputs.push(
+            CellOutput::new_builder()
+                .capacity(500u64.pack())
+                .lock(lock_script.clone())
+                .type_(Some(info_type_script.clone()).pack())
+                .build(),
+        );
+        outputs_data.push(build_versioned_time_info_cell_data(
+            INFO_DATA_VERSION,
+            *index,
+            TIMESTAMP_KIND,
+            1614828683123,
+        ));
+    }
+    // The capacity of the burnt cells is returned to the owner
+    let inputs_capacity = 50000u64 + 500u64 * (info_inputs_indexes.len() as u64 + 1);
+    let change_capacity = inputs_capacity - 500u64 * outputs.len() as u64;
+    outputs.push(
+        CellOutput::new_builder()
+            .capacity(change_capacity.pack())
+            .lock(lock_script)
+            .build(),
+    );
+    outputs_data.push(Bytes::new());
+
+    let witnesses = vec![Bytes::new(); inputs.len()];
+
+    // build transaction
+    let tx = TransactionBuilder::default()
+        .inputs(inputs)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .cell_dep(lock_script_dep)
+        .cell_dep(index_state_type_script_dep)
+        .cell_dep(info_type_script_dep)
+        .witnesses(witnesses.pack())
+        .build();
+    (context, tx)
+}
+
+// The lock hash of the inputs which are built by the test contexts
+fn always_success_lock_hash() -> [u8; 32] {
+    let lock_script = Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS))
+        .hash_type(ScriptHashType::Data.into())
+        .build();
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(lock_script.calc_script_hash().as_slice());
+    lock_hash
+}
+
+#[test]
+fn test_burn_info_cells_with_index_state_success() {
+    let (mut context, tx) = create_burn_test_context(
+        Some(always_success_lock_hash()),
+        SUM_OF_TIME_INFO_CELLS as usize,
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: false,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_burn_info_cells_with_index_state_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_error_burn_info_cells_index_state_not_burnt() {
+    let (mut context, tx) = create_burn_test_context(None, SUM_OF_TIME_INFO_CELLS as usize, true);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The time info cells are burnt when the index of the kept time index state cell is rotated,
+    // which the index state type script rejects before the info type script
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TIME_INFO_NOT_UPDATED)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_burn_info_cells_not_all_burnt() {
+    // The time info cell of another info type args is burnt instead of the last time info cell
+    // of the ring, so the index state type script counts all the time info cells but the info
+    // type script of the ring fails
+    let (mut context, tx) = create_burn_test_context(
+        Some(always_success_lock_hash()),
+        SUM_OF_TIME_INFO_CELLS as usize - 1,
+        false,
+    );
+    let info_input = tx.inputs().get(1).expect("info input");
+    let (info_output, info_data) = context
+        .get_cell(&info_input.previous_output())
+        .expect("get cell");
+    let info_type_script = info_output.type_().to_opt().expect("info type script");
+    let info_type_args: Bytes = info_type_script.args().unpack();
+    let mut another_info_type_args = InfoTypeArgs::from_slice(&info_type_args).expect("args");
+    another_info_type_args.flags |= HEADER_DEP_PROOF_FLAG;
+    let another_info_type_script = info_type_script
+        .as_builder()
+        .args(Bytes::from(another_info_type_args.to_vec()).pack())
+        .build();
+    let another_info_out_point = context.create_cell(
+        info_output
+            .as_builder()
+            .type_(Some(another_info_type_script).pack())
+            .build(),
+        info_data,
+    );
+    let tx = tx
+        .as_advanced_builder()
+        .input(
+            CellInput::new_builder()
+                .previous_output(another_info_out_point)
+                .build(),
+        )
+        .witness(Bytes::new().pack())
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_CELLS_NOT_ALL_BURNT)
+            .input_type_script(script_cell_index)
+    );
+}
+
+// The time info cells with the indexes are consumed and created without the time index state
+// cell, so the info type script is the only type script of the transaction
+fn create_test_context_without_index_state(
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    info_inputs_indexes: &[u8],
+    info_outputs_indexes: &[u8],
+) -> (Context, TransactionView) {
+    let index_state_data = build_versioned_index_state_cell_data(3);
+    let (context, tx) = create_test_context_with_info_cells(
+        owner_lock_hash_opt,
+        index_state_data.clone(),
+        Some(index_state_data),
+        info_inputs_indexes,
+        info_outputs_indexes,
+    );
+    let inputs: Vec<CellInput> = tx.inputs().into_iter().skip(1).collect();
+    let outputs: Vec<CellOutput> = tx.outputs().into_iter().skip(1).collect();
+    let outputs_data: Vec<Bytes> = tx
+        .outputs_data()
+        .into_iter()
+        .skip(1)
+        .map(|data| data.unpack())
+        .collect();
+    let witnesses = vec![Bytes::new(); inputs.len()];
+    let tx = tx
+        .as_advanced_builder()
+        .set_inputs(inputs)
+        .set_outputs(outputs)
+        .set_outputs_data(outputs_data.pack())
+        .set_witnesses(witnesses.pack())
+        .build();
+    (context, tx)
+}
+
+#[test]
+fn test_error_info_inputs_amount() {
+    let (mut context, tx) = create_test_context_without_index_state(None, &[1, 2], &[1]);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_INPUTS_AMOUNT_ERROR)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_info_outputs_amount() {
+    let (mut context, tx) = create_test_context_without_index_state(None, &[1], &[1, 2]);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_OUTPUTS_AMOUNT_ERROR)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_burn_info_cells_without_owner() {
+    // The time index state cell without the owner keeps the legacy destroy rules, but its time
+    // info cells can't be burnt
+    let (mut context, tx) = create_burn_test_context(None, SUM_OF_TIME_INFO_CELLS as usize, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_OWNER_NOT_EXIST)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_destroy_single_info_cell() {
+    // A single time info cell of the oracle with the owner can't be destroyed without the time
+    // index state cell
+    let (mut context, tx) =
+        create_test_context_without_index_state(Some(always_success_lock_hash()), &[1], &[]);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TYPE_NOT_EXIST)
+            .input_type_script(script_cell_index)
+    );
+}
+
+fn build_versioned_index_state_cell_data_with_sum(index: u8, sum: u8) -> Bytes {
+    Bytes::from(IndexStateData::new_versioned(index, sum).to_vec())
+}
+
+#[test]
+fn test_grow_info_cells_success() {
+    let (mut context, tx) = create_test_context_with_info_cells(
+        Some(always_success_lock_hash()),
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data_with_sum(3, 15)),
+        &[],
+        &[14, 12, 13],
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // dump raw test tx files
+    let setup = RunningSetup {
+        is_lock_script: false,
+        is_output: true,
+        script_index: 1,
+        native_binaries: HashMap::default(),
+    };
+    write_native_setup(
+        "test_grow_info_cells_success",
+        "ckb-time-info-type-sim",
+        &tx,
+        &context,
+        &setup,
+    );
+}
+
+#[test]
+fn test_shrink_info_cells_success() {
+    let (mut context, tx) = create_test_context_with_info_cells(
+        Some(always_success_lock_hash()),
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data_with_sum(3, 10)),
+        &[10, 11],
+        &[],
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_grow_info_cells_slot_duplicated() {
+    let (mut context, tx) = create_test_context_with_info_cells(
+        Some(always_success_lock_hash()),
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data_with_sum(3, 14)),
+        &[],
+        &[12, 12],
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_SLOT_DUPLICATED)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_shrink_info_cells_index_out_of_bound() {
+    // Only the time info cells of the removed slots can be burnt
+    let (mut context, tx) = create_test_context_with_info_cells(
+        Some(always_success_lock_hash()),
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data_with_sum(3, 10)),
+        &[9, 11],
+        &[],
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_INDEX_OUT_OF_BOUND)
+            .input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_info_slot_changed() {
+    // The time info cell of slot 6 can't be moved to slot 7
+    let inputs_data = vec![
+        build_index_state_cell_data(6, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 6, TIMESTAMP_KIND, 1614828683123),
+    ];
+    let outputs_data = vec![
+        build_index_state_cell_data(7, false),
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, 7, TIMESTAMP_KIND, 1614829080456),
+    ];
+    let since = SINCE_TIMESTAMP_BASE + 1614829080;
+    let (mut context, tx) =
+        create_test_context_with_info_inputs(&inputs_data, &outputs_data, since, false, None, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_SLOT_CHANGED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_create_info_cells_slot_missing() {
+    let mut outputs_data = build_genesis_outputs_data(|index| {
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, index, TIMESTAMP_KIND, 1614828683123)
+    });
+    outputs_data.pop();
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The time index state type script rejects the ring of the time info cells too
+    let script_cell_index = 1;
+    assert_any_type_script_error(
+        err,
+        &[
+            (TIME_INFO_SLOT_MISSING, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
+    );
+}
+
+// The output with the info type script of the same code and time index state type hash, but
+// with the header dep proof flag in the args
+fn build_info_output_with_other_args(output: &CellOutput) -> CellOutput {
+    let info_type_script = output.type_().to_opt().expect("info type script");
+    let mut args = info_type_script.args().raw_data().to_vec();
+    args.push(HEADER_DEP_PROOF_FLAG);
+    let info_type_script = info_type_script
+        .as_builder()
+        .args(Bytes::from(args).pack())
+        .build();
+    output
+        .clone()
+        .as_builder()
+        .type_(Some(info_type_script).pack())
+        .build()
+}
+
+#[test]
+fn test_error_create_info_cells_of_two_rings() {
+    // The time info cells of other info type args can't build another ring of the same time
+    // index state cell
+    let outputs_data = build_genesis_outputs_data(|index| {
+        build_versioned_time_info_cell_data(INFO_DATA_VERSION, index, TIMESTAMP_KIND, 1614828683123)
+    });
+    let (mut context, tx) = create_test_context(&outputs_data, false, false);
+    let lock_script = tx.outputs().get(0).expect("index state output").lock();
+    let normal_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity((500u64 * SUM_OF_TIME_INFO_CELLS as u64).pack())
+            .lock(lock_script)
+            .build(),
+        Bytes::new(),
+    );
+    let mut builder = tx
+        .as_advanced_builder()
+        .input(
+            CellInput::new_builder()
+                .previous_output(normal_input_out_point)
+                .build(),
+        )
+        .witness(Bytes::new().pack());
+    for (output, data) in tx.outputs_with_data_iter().skip(1) {
+        builder = builder
+            .output(build_info_output_with_other_args(&output))
+            .output_data(data.pack());
+    }
+
+    let tx = context.complete_tx(builder.build());
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The time index state type script rejects the ring of the time info cells too
+    let script_cell_index = 1;
+    assert_any_type_script_error(
+        err,
+        &[
+            (TIME_INFO_TYPE_ARGS_NOT_SAME, script_cell_index),
+            (INDEX_STATE_TIME_INFO_CELLS_NOT_CREATED, 0),
+        ],
+    );
+}
+
+#[test]
+fn test_error_grow_info_cells_type_args_not_same() {
+    // The time info cell of slot 13 has other info type args
+    let (mut context, tx) = create_test_context_with_info_cells(
+        Some(always_success_lock_hash()),
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data_with_sum(3, 14)),
+        &[],
+        &[12, 13],
+    );
+    let outputs: Vec<_> = tx
+        .outputs()
+        .into_iter()
+        .enumerate()
+        .map(|(index, output)| {
+            if index == 2 {
+                build_info_output_with_other_args(&output)
+            } else {
+                output
+            }
+        })
+        .collect();
+    let tx = tx.as_advanced_builder().set_outputs(outputs).build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 1;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_TYPE_ARGS_NOT_SAME)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_create_info_cells_after_genesis() {
+    // The time info cell of slot 4 is created when the time index state cell is updated
+    let (mut context, tx) = create_test_context_with_info_cells(
+        None,
+        build_versioned_index_state_cell_data(3),
+        Some(build_versioned_index_state_cell_data(4)),
+        &[],
+        &[4],
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    // The index state type script runs first and finds no time info cell to be updated
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_TIME_INFO_NOT_UPDATED)
+            .input_type_script(script_cell_index)
+    );
+}
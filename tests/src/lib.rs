@@ -17,12 +17,33 @@ use std::str::FromStr;
 
 use ckb_tool::ckb_types::core::{DepType, TransactionView};
 
+#[cfg(test)]
+mod cli_tests;
+
 #[cfg(test)]
 mod info_tests;
 
 #[cfg(test)]
 mod index_state_tests;
 
+#[cfg(test)]
+mod sdk_tests;
+
+#[cfg(test)]
+mod time_lock_tests;
+
+#[cfg(test)]
+mod time_window_tests;
+
+#[cfg(test)]
+mod types_tests;
+
+#[cfg(test)]
+mod updater_tests;
+
+#[cfg(test)]
+mod util;
+
 lazy_static! {
     static ref LOADER: Loader = Loader::default();
     static ref TX_FOLDER: PathBuf = {
@@ -94,7 +115,16 @@ impl Loader {
     }
 }
 
-pub fn assert_type_script_error(err: Error, error_code: i8, script_cell_index: usize) {
+// The secp256k1 library of ckb-miscellaneous-scripts which is built by `make deps`
+pub fn load_secp256k1_lib() -> Bytes {
+    let mut path = env::current_dir().unwrap();
+    path.push("..");
+    path.push("deps");
+    path.push("secp256k1_blake2b_sighash_all_dual");
+    fs::read(path).expect("secp256k1 library").into()
+}
+
+fn is_type_script_error(error: &str, error_code: i8, script_cell_index: usize) -> bool {
     let input_type_error = Into::<Error>::into(
         ScriptError::ValidationFailure(error_code).input_type_script(script_cell_index),
     )
@@ -103,9 +133,24 @@ pub fn assert_type_script_error(err: Error, error_code: i8, script_cell_index: u
         ScriptError::ValidationFailure(error_code).output_type_script(script_cell_index),
     )
     .to_string();
+    input_type_error == error || output_type_error == error
+}
+
+pub fn assert_type_script_error(err: Error, error_code: i8, script_cell_index: usize) {
+    let error = Into::<Error>::into(err).to_string();
+    assert!(is_type_script_error(&error, error_code, script_cell_index));
+}
+
+// Both the time index state type script and the time info type script reject some transactions,
+// and the script groups are not verified in a fixed order, so either of the errors is returned
+pub fn assert_any_type_script_error(err: Error, expected_errors: &[(i8, usize)]) {
     let error = Into::<Error>::into(err).to_string();
-    let result = input_type_error == error || output_type_error == error;
-    assert!(result);
+    let result = expected_errors
+        .iter()
+        .any(|(error_code, script_cell_index)| {
+            is_type_script_error(&error, *error_code, *script_cell_index)
+        });
+    assert!(result, "unexpected error: {}", error);
 }
 
 fn create_test_folder(name: &str) -> PathBuf {
@@ -0,0 +1,314 @@
+use super::util::{
+    always_success_lock_hash, build_genesis_tx, commit_tx, create_funding_cell, deploy_oracle,
+    new_genesis_tx, new_timestamp_genesis_tx, Oracle, CKB, FEE, GENESIS_TIMESTAMP,
+    SUM_OF_TIME_INFO_CELLS,
+};
+use ckb_testtool::context::Context;
+use ckb_tool::ckb_types::{bytes::Bytes, prelude::*};
+use time_cell_types::{
+    rules::RuleError, type_id, IndexStateTypeArgs, InfoTypeArgs, TimeInfoData, TimeValue,
+};
+use time_oracle_sdk::{DestroyTx, Error, GenesisTx, LiveCell, UpdateTx};
+
+const MAX_CYCLES: u64 = 10_000_000;
+
+// The fee is paid by a new funding cell
+fn build_update_tx(
+    context: &mut Context,
+    oracle: &Oracle,
+    index_state_cell: &LiveCell,
+    info_cell: &LiveCell,
+    latest_info_cell: &LiveCell,
+    time: TimeValue,
+) -> UpdateTx {
+    UpdateTx {
+        codes: oracle.codes.clone(),
+        lock_deps: vec![oracle.lock_script_dep.clone()],
+        index_state_cell: index_state_cell.clone(),
+        info_cell: info_cell.clone(),
+        latest_info_cell: latest_info_cell.clone(),
+        time,
+        header_dep: None,
+        extra_inputs: vec![create_funding_cell(context, oracle)],
+        change_lock: oracle.lock_script.clone(),
+        fee: FEE,
+    }
+}
+
+#[test]
+fn test_sdk_genesis_tx_success() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let tx = build_genesis_tx(&mut context, &oracle, None);
+
+    // index state cell, time info cells and change cell
+    assert_eq!(tx.outputs().len(), SUM_OF_TIME_INFO_CELLS as usize + 2);
+    // The index state type args commit the info type code
+    let index_state_type = tx
+        .outputs()
+        .get(0)
+        .and_then(|output| output.type_().to_opt());
+    let index_state_args: Bytes = index_state_type.expect("index state type").args().unpack();
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&index_state_args)
+            .expect("index state args")
+            .info_type_code,
+        Some(oracle.codes.info_type_code())
+    );
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_genesis_tx_type_id_success() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let tx = GenesisTx {
+        owner_lock_hash: Some(always_success_lock_hash()),
+        type_id: true,
+        ..new_timestamp_genesis_tx(&mut context, &oracle)
+    }
+    .build()
+    .expect("genesis tx");
+
+    // The index state type args are the Type ID of the output 0
+    let index_state_type = tx
+        .outputs()
+        .get(0)
+        .and_then(|output| output.type_().to_opt());
+    let index_state_args: Bytes = index_state_type.expect("index state type").args().unpack();
+    let first_input = tx.inputs().get(0).expect("first input");
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&index_state_args),
+        Ok(IndexStateTypeArgs::new_type_id(
+            type_id(first_input.as_slice(), 0),
+            Some(oracle.codes.info_type_code()),
+            Some(always_success_lock_hash()),
+        ))
+    );
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_update_tx_success() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let genesis_tx = build_genesis_tx(&mut context, &oracle, None);
+    context
+        .verify_tx(&genesis_tx, MAX_CYCLES)
+        .expect("pass verification");
+    let cells = commit_tx(&mut context, &genesis_tx);
+
+    // The next index is 1 and the time info cell of the slot 1 is the output 2 of genesis, and
+    // the latest time info cell of the slot 0 is the output 1
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    let tx = build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+        .build()
+        .expect("update tx");
+    let since: u64 = tx.inputs().get(1).expect("info input").since().unpack();
+    assert_eq!(since, time.since());
+    // The fee is paid by the funding cell, and the oracle cells keep their capacity
+    let index_state_capacity: u64 = tx.outputs().get(0).expect("output").capacity().unpack();
+    assert_eq!(index_state_capacity, cells[0].capacity());
+    let info_capacity: u64 = tx.outputs().get(1).expect("output").capacity().unpack();
+    assert_eq!(info_capacity, cells[2].capacity());
+    let change_capacity: u64 = tx.outputs().get(2).expect("change").capacity().unpack();
+    assert_eq!(change_capacity, 1_000_000 * CKB - FEE);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+
+    // The next update goes on from the outputs of the last update
+    let updated_cells = commit_tx(&mut context, &tx);
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 120_000);
+    let tx = build_update_tx(
+        &mut context,
+        &oracle,
+        &updated_cells[0],
+        &cells[3],
+        &updated_cells[1],
+        time,
+    )
+    .build()
+    .expect("update tx");
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_update_tx_refills_legacy_info_cell() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let funding_cell = create_funding_cell(&mut context, &oracle);
+    let genesis_tx = new_genesis_tx(
+        &oracle,
+        funding_cell,
+        InfoTypeArgs::new([0u8; 32]),
+        |index| TimeInfoData::Timestamp {
+            index,
+            timestamp: (GENESIS_TIMESTAMP / 1000) as u32 + index as u32,
+        },
+    )
+    .build()
+    .expect("genesis tx");
+    context
+        .verify_tx(&genesis_tx, MAX_CYCLES)
+        .expect("pass verification");
+    let cells = commit_tx(&mut context, &genesis_tx);
+
+    // The legacy time info cell of the slot 1 is migrated to the versioned layout, whose data
+    // is 6 bytes longer than the occupied capacity given by the genesis
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    let tx = build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+        .build()
+        .expect("update tx");
+    let info_capacity: u64 = tx.outputs().get(1).expect("output").capacity().unpack();
+    assert_eq!(info_capacity, cells[2].capacity() + 6 * CKB);
+    let change_capacity: u64 = tx.outputs().get(2).expect("change").capacity().unpack();
+    assert_eq!(change_capacity, 1_000_000 * CKB - 6 * CKB - FEE);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_update_tx_error() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let genesis_tx = build_genesis_tx(&mut context, &oracle, None);
+    let cells = commit_tx(&mut context, &genesis_tx);
+
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    assert_eq!(
+        build_update_tx(&mut context, &oracle, &cells[0], &cells[3], &cells[1], time)
+            .build()
+            .unwrap_err(),
+        Error::InfoSlotNotNext
+    );
+    assert_eq!(
+        build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[3], time)
+            .build()
+            .unwrap_err(),
+        Error::InfoSlotNotLatest
+    );
+
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP);
+    assert_eq!(
+        build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+            .build()
+            .unwrap_err(),
+        Error::Rule(RuleError::TimeNotBigger)
+    );
+
+    let time = TimeValue::BlockNumber(GENESIS_TIMESTAMP + 60_000);
+    assert_eq!(
+        build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+            .build()
+            .unwrap_err(),
+        Error::Rule(RuleError::TimeInfoKindNotSame)
+    );
+
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 60_000);
+    let mut update_tx =
+        build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time);
+    update_tx.fee = update_tx.extra_inputs[0].capacity() + 1;
+    assert_eq!(update_tx.build().unwrap_err(), Error::InsufficientCapacity);
+
+    // The time of the next update is bigger than the overwritten time of the slot 2, but not
+    // the latest time of the slot 1
+    let tx = build_update_tx(&mut context, &oracle, &cells[0], &cells[2], &cells[1], time)
+        .build()
+        .expect("update tx");
+    let updated_cells = commit_tx(&mut context, &tx);
+    let time = TimeValue::TimestampMillis(GENESIS_TIMESTAMP + 30_000);
+    assert_eq!(
+        build_update_tx(
+            &mut context,
+            &oracle,
+            &updated_cells[0],
+            &cells[3],
+            &updated_cells[1],
+            time
+        )
+        .build()
+        .unwrap_err(),
+        Error::Rule(RuleError::TimeNotBigger)
+    );
+}
+
+#[test]
+fn test_sdk_destroy_tx_success() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let owner_lock_hash = always_success_lock_hash();
+    let genesis_tx = build_genesis_tx(&mut context, &oracle, Some(owner_lock_hash));
+    let cells = commit_tx(&mut context, &genesis_tx);
+
+    let tx = DestroyTx {
+        codes: oracle.codes.clone(),
+        lock_deps: vec![oracle.lock_script_dep.clone()],
+        index_state_cell: cells[0].clone(),
+        info_cells: cells[1..=SUM_OF_TIME_INFO_CELLS as usize].to_vec(),
+        extra_inputs: vec![],
+        change_lock: oracle.lock_script.clone(),
+        fee: FEE,
+    }
+    .build()
+    .expect("destroy tx");
+    assert_eq!(tx.outputs().len(), 1);
+
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_sdk_destroy_tx_not_all_burnt_error() {
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let genesis_tx = build_genesis_tx(&mut context, &oracle, None);
+    let cells = commit_tx(&mut context, &genesis_tx);
+
+    let destroy_tx = DestroyTx {
+        codes: oracle.codes.clone(),
+        lock_deps: vec![oracle.lock_script_dep.clone()],
+        index_state_cell: cells[0].clone(),
+        info_cells: cells[1..SUM_OF_TIME_INFO_CELLS as usize].to_vec(),
+        extra_inputs: vec![],
+        change_lock: oracle.lock_script.clone(),
+        fee: FEE,
+    };
+    assert_eq!(
+        destroy_tx.build().unwrap_err(),
+        Error::TimeCell(time_cell_types::Error::TimeInfoRingIncomplete)
+    );
+
+    // The time info cells of the oracle without an owner can't be burnt
+    let mut destroy_tx = destroy_tx;
+    destroy_tx.info_cells = cells[1..=SUM_OF_TIME_INFO_CELLS as usize].to_vec();
+    assert_eq!(
+        destroy_tx.build().unwrap_err(),
+        Error::IndexStateOwnerNotExist
+    );
+}
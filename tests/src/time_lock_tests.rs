@@ -0,0 +1,483 @@
+use super::util::{
+    always_success_lock_hash, build_block_number_info_cell_data,
+    build_legacy_timestamp_info_cell_data, build_timestamp_info_cell_data, create_consumer_context,
+    LATEST_BLOCK_NUMBER, LATEST_INDEX, LATEST_TIMESTAMP, SUM_OF_TIME_INFO_CELLS,
+};
+use ckb_testtool::context::Context;
+use ckb_tool::ckb_error::assert_error_eq;
+use ckb_tool::ckb_script::ScriptError;
+use ckb_tool::ckb_types::{bytes::Bytes, core::TransactionView, packed::*, prelude::*};
+use time_cell_types::{BLOCK_NUMBER_KIND, TIMESTAMP_KIND, TIME_LOCK_RING_MEDIAN_FLAG};
+
+const MAX_CYCLES: u64 = 10_000_000;
+
+// error numbers
+const INVALID_ARGUMENT: i8 = 5;
+const INDEX_STATE_NOT_FOUND: i8 = 7;
+const TIME_KIND_NOT_SAME: i8 = 13;
+const TIME_NOT_REACHED: i8 = 14;
+const OWNER_NOT_AUTHORIZED: i8 = 15;
+const LATEST_TIME_INFO_DUPLICATED: i8 = 16;
+const TIME_INFO_RING_INCOMPLETE: i8 = 17;
+
+// Time lock args: info type script hash(32 bytes) | kind(u8) | threshold(u64)
+//                 | owner lock hash(32 bytes) | flags(u8, optional), and the args without the
+// owner lock hash are built for the error case
+fn build_time_lock_args(
+    info_type_hash: &[u8],
+    kind: u8,
+    threshold: u64,
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    flags: u8,
+) -> Bytes {
+    let mut args = info_type_hash.to_vec();
+    args.push(kind);
+    args.extend_from_slice(&threshold.to_be_bytes());
+    if let Some(owner_lock_hash) = owner_lock_hash_opt {
+        args.extend_from_slice(&owner_lock_hash);
+    }
+    if flags != 0 {
+        args.push(flags);
+    }
+    Bytes::from(args)
+}
+
+fn create_test_context<F>(
+    build_info_data: F,
+    kind: u8,
+    threshold: u64,
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    is_index_state_dep_missing: bool,
+) -> (Context, TransactionView)
+where
+    F: Fn(u8) -> Bytes,
+{
+    create_test_context_with_flags(
+        build_info_data,
+        kind,
+        threshold,
+        owner_lock_hash_opt,
+        is_index_state_dep_missing,
+        0,
+    )
+}
+
+// The time index state cell and the time info cells of the oracle are the cell deps, and the
+// first input is locked by the time lock
+fn create_test_context_with_flags<F>(
+    build_info_data: F,
+    kind: u8,
+    threshold: u64,
+    owner_lock_hash_opt: Option<[u8; 32]>,
+    is_index_state_dep_missing: bool,
+    flags: u8,
+) -> (Context, TransactionView)
+where
+    F: Fn(u8) -> Bytes,
+{
+    let mut context = Context::default();
+    let mut consumer = create_consumer_context(&mut context, "time-lock", build_info_data);
+    if is_index_state_dep_missing {
+        consumer.oracle_deps.remove(0);
+    }
+
+    let time_lock_args = build_time_lock_args(
+        &consumer.info_type_hash,
+        kind,
+        threshold,
+        owner_lock_hash_opt,
+        flags,
+    );
+    let time_lock_script = context
+        .build_script(&consumer.consumer_out_point, time_lock_args)
+        .expect("script");
+
+    // prepare cells
+    let time_lock_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(time_lock_script)
+            .build(),
+        Bytes::new(),
+    );
+    let normal_input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(consumer.lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let inputs = vec![
+        CellInput::new_builder()
+            .previous_output(time_lock_input_out_point)
+            .build(),
+        CellInput::new_builder()
+            .previous_output(normal_input_out_point)
+            .build(),
+    ];
+    let outputs = vec![CellOutput::new_builder()
+        .capacity(1500u64.pack())
+        .lock(consumer.lock_script.clone())
+        .build()];
+    let tx = consumer.build_tx(inputs, outputs);
+    (context, tx)
+}
+
+#[test]
+fn test_unlock_time_lock_with_timestamp_success() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP - 1000,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_unlock_time_lock_with_legacy_timestamp_success() {
+    // The legacy timestamp in seconds is compared in milliseconds
+    let (mut context, tx) = create_test_context(
+        build_legacy_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP / 1000 * 1000,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_unlock_time_lock_with_block_number_success() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_unlock_time_lock_with_owner_success() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER - 1,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_time_lock_time_not_reached() {
+    // The time of the older slots must not be used
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP + 1,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_NOT_REACHED).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_kind_not_same() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_KIND_NOT_SAME).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_invalid_kind() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        3,
+        LATEST_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_index_state_not_found() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INDEX_STATE_NOT_FOUND).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_without_owner() {
+    // The cell without an owner could be spent by anyone once the threshold is reached
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP,
+        None,
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_owner_not_authorized() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER,
+        Some([1u8; 32]),
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(OWNER_NOT_AUTHORIZED).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_latest_time_info_duplicated() {
+    // Another time info cell dep of the latest slot makes the latest time ambiguous
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        false,
+    );
+    let info_cell_dep = tx
+        .cell_deps()
+        .get(tx.cell_deps().len() - 1)
+        .expect("info cell dep");
+    let (info_output, _) = context
+        .get_cell(&info_cell_dep.out_point())
+        .expect("info cell");
+    let info_out_point =
+        context.create_cell(info_output, build_timestamp_info_cell_data(LATEST_INDEX));
+    let tx = tx
+        .as_advanced_builder()
+        .cell_dep(CellDep::new_builder().out_point(info_out_point).build())
+        .build();
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(LATEST_TIME_INFO_DUPLICATED)
+            .input_lock_script(script_cell_index)
+    );
+}
+
+// The cell deps are the lock, the time lock, the time index state cell and the time info cells
+// of the slots in order
+const FIRST_INFO_CELL_DEP_INDEX: usize = 3;
+
+// The time info cell dep of the slot is removed if info_data_opt is none, otherwise it's replaced
+// by another time info cell with the data
+fn replace_info_cell_dep(
+    context: &mut Context,
+    tx: TransactionView,
+    slot: u8,
+    info_data_opt: Option<Bytes>,
+) -> TransactionView {
+    let position = FIRST_INFO_CELL_DEP_INDEX + slot as usize;
+    let mut cell_deps: Vec<CellDep> = tx.cell_deps().into_iter().collect();
+    let info_cell_dep = cell_deps.remove(position);
+    if let Some(info_data) = info_data_opt {
+        let (info_output, _) = context
+            .get_cell(&info_cell_dep.out_point())
+            .expect("info cell");
+        let info_out_point = context.create_cell(info_output, info_data);
+        cell_deps.insert(
+            position,
+            CellDep::new_builder().out_point(info_out_point).build(),
+        );
+    }
+    tx.as_advanced_builder().set_cell_deps(cell_deps).build()
+}
+
+// The median of the ring is the time of the slot which is 6 updates older than the latest one
+const MEDIAN_TIMESTAMP: u64 = LATEST_TIMESTAMP - 6 * 60_000;
+
+#[test]
+fn test_unlock_time_lock_with_ring_median_success() {
+    let (mut context, tx) = create_test_context_with_flags(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        MEDIAN_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        false,
+        TIME_LOCK_RING_MEDIAN_FLAG,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_time_lock_ring_median_not_reached() {
+    // The latest time has reached the threshold, but the median hasn't
+    let (mut context, tx) = create_test_context_with_flags(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        MEDIAN_TIMESTAMP + 1,
+        Some(always_success_lock_hash()),
+        false,
+        TIME_LOCK_RING_MEDIAN_FLAG,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_NOT_REACHED).input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_ring_slot_missing() {
+    let (mut context, tx) = create_test_context_with_flags(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        MEDIAN_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        false,
+        TIME_LOCK_RING_MEDIAN_FLAG,
+    );
+    let tx = replace_info_cell_dep(&mut context, tx, SUM_OF_TIME_INFO_CELLS - 1, None);
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_RING_INCOMPLETE)
+            .input_lock_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_lock_ring_slot_duplicated() {
+    // The time info cell of slot 0 is replaced by another time info cell of slot 1
+    let (mut context, tx) = create_test_context_with_flags(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        MEDIAN_TIMESTAMP,
+        Some(always_success_lock_hash()),
+        false,
+        TIME_LOCK_RING_MEDIAN_FLAG,
+    );
+    let tx = replace_info_cell_dep(&mut context, tx, 0, Some(build_timestamp_info_cell_data(1)));
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_INFO_RING_INCOMPLETE)
+            .input_lock_script(script_cell_index)
+    );
+}
@@ -0,0 +1,224 @@
+use super::util::{
+    build_block_number_info_cell_data, build_timestamp_info_cell_data, create_consumer_context,
+    LATEST_BLOCK_NUMBER, LATEST_TIMESTAMP,
+};
+use ckb_testtool::context::Context;
+use ckb_tool::ckb_error::assert_error_eq;
+use ckb_tool::ckb_script::ScriptError;
+use ckb_tool::ckb_types::{bytes::Bytes, core::TransactionView, packed::*, prelude::*};
+use time_cell_types::{BLOCK_NUMBER_KIND, TIMESTAMP_KIND};
+
+const MAX_CYCLES: u64 = 10_000_000;
+
+// error numbers
+const INVALID_ARGUMENT: i8 = 5;
+const TIME_KIND_NOT_SAME: i8 = 13;
+const TIME_WINDOW_NOT_STARTED: i8 = 14;
+const TIME_WINDOW_CLOSED: i8 = 15;
+
+// Time window type args: info type script hash(32 bytes) | kind(u8) | start(u64) | end(u64)
+fn build_time_window_args(info_type_hash: &[u8], kind: u8, start: u64, end: u64) -> Bytes {
+    let mut args = info_type_hash.to_vec();
+    args.push(kind);
+    args.extend_from_slice(&start.to_be_bytes());
+    args.extend_from_slice(&end.to_be_bytes());
+    Bytes::from(args)
+}
+
+// The time index state cell and the time info cells of the oracle are the cell deps, and the
+// cell guarded by the time window type script is created if it is the output, destroyed if it is
+// the input, or transferred if it is both
+fn create_test_context<F>(
+    build_info_data: F,
+    kind: u8,
+    start: u64,
+    end: u64,
+    is_window_cell_input: bool,
+    is_window_cell_output: bool,
+) -> (Context, TransactionView)
+where
+    F: Fn(u8) -> Bytes,
+{
+    let mut context = Context::default();
+    let consumer = create_consumer_context(&mut context, "time-window-type", build_info_data);
+
+    let time_window_args = build_time_window_args(&consumer.info_type_hash, kind, start, end);
+    let time_window_type_script = context
+        .build_script(&consumer.consumer_out_point, time_window_args)
+        .expect("script");
+
+    // prepare cells
+    let normal_cell = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(consumer.lock_script.clone())
+        .build();
+    let window_cell = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(consumer.lock_script.clone())
+        .type_(Some(time_window_type_script).pack())
+        .build();
+    let input_cell = if is_window_cell_input {
+        window_cell.clone()
+    } else {
+        normal_cell.clone()
+    };
+    let output_cell = if is_window_cell_output {
+        window_cell
+    } else {
+        normal_cell
+    };
+    let input_out_point = context.create_cell(input_cell, Bytes::new());
+    let inputs = vec![CellInput::new_builder()
+        .previous_output(input_out_point)
+        .build()];
+    let tx = consumer.build_tx(inputs, vec![output_cell]);
+    (context, tx)
+}
+
+#[test]
+fn test_create_cell_in_timestamp_window_success() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP - 1000,
+        LATEST_TIMESTAMP + 1000,
+        false,
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_destroy_cell_in_block_number_window_success() {
+    // Both the start and the end are inclusive
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER,
+        LATEST_BLOCK_NUMBER,
+        true,
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+#[test]
+fn test_error_time_window_not_started() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP + 1,
+        LATEST_TIMESTAMP + 1000,
+        false,
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_WINDOW_NOT_STARTED)
+            .output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_destroy_cell_after_time_window_success() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER - 100,
+        LATEST_BLOCK_NUMBER - 1,
+        true,
+        false,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let cycles = context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+    println!("consume cycles: {}", cycles);
+}
+
+// The cell can't be transferred after the end of the window
+#[test]
+fn test_error_time_window_closed() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        BLOCK_NUMBER_KIND,
+        LATEST_BLOCK_NUMBER - 100,
+        LATEST_BLOCK_NUMBER - 1,
+        true,
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_WINDOW_CLOSED).input_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_window_kind_not_same() {
+    let (mut context, tx) = create_test_context(
+        build_block_number_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP - 1000,
+        LATEST_TIMESTAMP + 1000,
+        false,
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(TIME_KIND_NOT_SAME).output_type_script(script_cell_index)
+    );
+}
+
+#[test]
+fn test_error_time_window_start_bigger_than_end() {
+    let (mut context, tx) = create_test_context(
+        build_timestamp_info_cell_data,
+        TIMESTAMP_KIND,
+        LATEST_TIMESTAMP + 1000,
+        LATEST_TIMESTAMP - 1000,
+        false,
+        true,
+    );
+
+    let tx = context.complete_tx(tx);
+    // run
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+
+    let script_cell_index = 0;
+    assert_error_eq!(
+        err,
+        ScriptError::ValidationFailure(INVALID_ARGUMENT).output_type_script(script_cell_index)
+    );
+}
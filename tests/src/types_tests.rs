@@ -0,0 +1,618 @@
+use ckb_tool::ckb_types::{bytes::Bytes, core::ScriptHashType, packed::Script, prelude::*};
+use time_cell_types::{
+    epoch_with_fraction,
+    rules::{
+        check_index_state, check_index_state_migrated, check_index_state_updated, check_info_slots,
+        check_info_time, check_info_updated, RuleError,
+    },
+    script_hash, type_id, Error, IndexStateData, IndexStateId, IndexStateTypeArgs, InfoTypeArgs,
+    InfoTypeCode, RingTime, TimeInfoData, TimeKind, TimeLockArgs, TimeValue, TimeWindowArgs,
+    HEADER_DEP_PROOF_FLAG, MULTI_SIG_FLAG, SINCE_EPOCH_BASE, SINCE_TIMESTAMP_BASE,
+};
+
+#[test]
+fn test_index_state_data_round_trip() {
+    let index_state = IndexStateData::new(3, 12);
+    let data = index_state.to_vec();
+    assert_eq!(data, vec![3, 12]);
+    assert_eq!(IndexStateData::from_slice(&data), Ok(index_state));
+    assert_eq!(
+        IndexStateData::from_slice(&[0, 12, 0]),
+        Err(Error::IndexStateDataLenError)
+    );
+}
+
+#[test]
+fn test_versioned_index_state_data_round_trip() {
+    let index_state = IndexStateData::new_versioned(3, 12);
+    let data = index_state.to_vec();
+    assert_eq!(data, vec![1, 3, 12]);
+    assert_eq!(IndexStateData::from_slice(&data), Ok(index_state));
+    assert_eq!(
+        IndexStateData::from_slice(&[0, 3, 12]),
+        Err(Error::IndexStateDataLenError)
+    );
+    assert_eq!(
+        IndexStateData::from_slice(&[1, 3, 12, 0]),
+        Err(Error::IndexStateDataLenError)
+    );
+    assert_eq!(
+        IndexStateData::from_slice(&[3, 3, 12]),
+        Err(Error::InvalidIndexStateVersion)
+    );
+}
+
+#[test]
+fn test_index_state_data_with_updaters_round_trip() {
+    let index_state = IndexStateData::new_with_updaters(3, 12, vec![[1u8; 32], [2u8; 32]]);
+    let data = index_state.to_vec();
+    // The version is followed by the molecule table of the index, the sum and the updaters
+    assert_eq!(data.len(), 1 + 16 + 2 + 4 + 64);
+    assert_eq!(&data[..5], &[2, 86, 0, 0, 0]);
+    assert_eq!(&data[17..23], &[3, 12, 2, 0, 0, 0]);
+    assert_eq!(IndexStateData::from_slice(&data), Ok(index_state.clone()));
+    assert!(index_state.is_updater_authorized(&[2u8; 32]));
+    assert!(!index_state.is_updater_authorized(&[3u8; 32]));
+    assert!(IndexStateData::new_versioned(3, 12).is_updater_authorized(&[3u8; 32]));
+    assert_eq!(
+        IndexStateData::from_slice(&data[..data.len() - 1]),
+        Err(Error::IndexStateDataLenError)
+    );
+    assert_eq!(
+        IndexStateData::from_slice(&IndexStateData::new_with_updaters(3, 12, vec![]).to_vec()),
+        Err(Error::IndexStateUpdatersEmpty)
+    );
+}
+
+#[test]
+fn test_index_state_data_next_index() {
+    assert_eq!(IndexStateData::new(3, 12).next_index(), 4);
+    assert_eq!(IndexStateData::new(11, 12).next_index(), 0);
+    assert_eq!(IndexStateData::new(255, 255).next_index(), 0);
+}
+
+#[test]
+fn test_time_info_data_round_trip() {
+    let infos = vec![
+        TimeInfoData::Timestamp {
+            index: 1,
+            timestamp: 1614828683,
+        },
+        TimeInfoData::BlockNumber {
+            index: 2,
+            block_number: 10000,
+        },
+        TimeInfoData::Versioned {
+            index: 6,
+            kind: TimeKind::Timestamp,
+            time: 1614828683123,
+        },
+        TimeInfoData::Versioned {
+            index: 0,
+            kind: TimeKind::Epoch,
+            time: epoch_with_fraction(1000, 900, 1800),
+        },
+    ];
+    for info in infos {
+        let data = info.to_vec();
+        assert_eq!(data.len() != 11, info.version() == 0);
+        assert_eq!(data.len(), info.data_len());
+        assert_eq!(TimeInfoData::from_slice(&data), Ok(info));
+    }
+    assert_eq!(
+        hex::encode(
+            TimeInfoData::Versioned {
+                index: 6,
+                kind: TimeKind::Timestamp,
+                time: 1614828683123,
+            }
+            .to_vec()
+        ),
+        "01060100000177fb4a3f73"
+    );
+}
+
+#[test]
+fn test_time_info_data_error() {
+    assert_eq!(
+        TimeInfoData::from_slice(&[0u8; 6]),
+        Err(Error::TimeInfoDataLenError)
+    );
+    let mut data = TimeInfoData::Versioned {
+        index: 0,
+        kind: TimeKind::BlockNumber,
+        time: 10000,
+    }
+    .to_vec();
+    data[2] = 3;
+    assert_eq!(
+        TimeInfoData::from_slice(&data),
+        Err(Error::InvalidTimeInfoKind)
+    );
+    data[0] = 2;
+    assert_eq!(
+        TimeInfoData::from_slice(&data),
+        Err(Error::InvalidTimeInfoVersion)
+    );
+    let data = TimeInfoData::Versioned {
+        index: 0,
+        kind: TimeKind::Epoch,
+        time: epoch_with_fraction(1000, 1800, 1800),
+    }
+    .to_vec();
+    assert_eq!(
+        TimeInfoData::from_slice(&data),
+        Err(Error::InvalidTimeInfoEpoch)
+    );
+}
+
+#[test]
+fn test_time_value_since_and_step() {
+    let input = TimeValue::TimestampMillis(1614828683123);
+    let output = TimeValue::TimestampMillis(1614829080456);
+    assert!(output.is_bigger_than(&input));
+    assert_eq!(output.step_from(&input), 397333);
+    assert_eq!(output.since(), SINCE_TIMESTAMP_BASE + 1614829080);
+
+    let input = TimeValue::Epoch(epoch_with_fraction(1000, 900, 1800));
+    let output = TimeValue::Epoch(epoch_with_fraction(1002, 0, 1800));
+    assert!(output.is_bigger_than(&input));
+    assert_eq!(output.step_from(&input), 2);
+    assert_eq!(
+        output.since(),
+        SINCE_EPOCH_BASE + epoch_with_fraction(1002, 0, 1800)
+    );
+
+    let legacy_timestamp = TimeValue::Timestamp(1614828683);
+    assert_eq!(
+        legacy_timestamp.to_versioned(),
+        TimeValue::TimestampMillis(1614828683000)
+    );
+
+    let block_number = TimeValue::BlockNumber(10000);
+    assert!(!block_number.is_same_kind(&input));
+    assert!(!block_number.is_bigger_than(&input));
+    assert_eq!(block_number.since(), 10000);
+}
+
+#[test]
+fn test_info_type_args_round_trip() {
+    let mut args = InfoTypeArgs::new([1u8; 32]);
+    assert_eq!(args.to_vec().len(), 32);
+    args.flags = HEADER_DEP_PROOF_FLAG;
+    assert_eq!(args.to_vec().len(), 33);
+    args.min_step = 1000;
+    args.max_step = 60000;
+    let data = args.to_vec();
+    assert_eq!(data.len(), 49);
+    let parsed = InfoTypeArgs::from_slice(&data).unwrap();
+    assert_eq!(parsed, args);
+    assert!(parsed.has_header_dep_proof());
+
+    args.min_step = 60001;
+    assert_eq!(
+        InfoTypeArgs::from_slice(&args.to_vec()),
+        Err(Error::InvalidInfoTypeArgs)
+    );
+    let mut data = InfoTypeArgs::new([1u8; 32]).to_vec();
+    data.push(0x02);
+    assert_eq!(
+        InfoTypeArgs::from_slice(&data),
+        Err(Error::InvalidInfoTypeArgs)
+    );
+}
+
+#[test]
+fn test_info_type_args_with_operators_round_trip() {
+    let mut args = InfoTypeArgs::new([1u8; 32]);
+    args.flags = MULTI_SIG_FLAG;
+    args.threshold = 2;
+    args.operators = vec![[2u8; 20], [3u8; 20], [4u8; 20]];
+    let data = args.to_vec();
+    assert_eq!(data.len(), 49 + 1 + 60);
+    let parsed = InfoTypeArgs::from_slice(&data).unwrap();
+    assert_eq!(parsed, args);
+    assert!(parsed.has_multi_sig());
+
+    args.threshold = 4;
+    assert_eq!(
+        InfoTypeArgs::from_slice(&args.to_vec()),
+        Err(Error::InvalidInfoTypeArgs)
+    );
+    args.threshold = 0;
+    assert_eq!(
+        InfoTypeArgs::from_slice(&args.to_vec()),
+        Err(Error::InvalidInfoTypeArgs)
+    );
+    // The operators must not exist without the multi-signature flag
+    let mut data = InfoTypeArgs::new([1u8; 32]).to_vec();
+    data.extend_from_slice(&[0u8; 17]);
+    data.push(1);
+    data.extend_from_slice(&[2u8; 20]);
+    assert_eq!(
+        InfoTypeArgs::from_slice(&data),
+        Err(Error::InvalidInfoTypeArgs)
+    );
+}
+
+#[test]
+fn test_index_state_type_args_round_trip() {
+    let args = IndexStateTypeArgs::new([1u8; 36], None, None);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 36);
+    assert_eq!(IndexStateTypeArgs::from_slice(&data), Ok(args));
+
+    let info_type_code = InfoTypeCode::new([3u8; 32], 1);
+    let args = IndexStateTypeArgs::new([1u8; 36], Some(info_type_code), None);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 69);
+    assert_eq!(IndexStateTypeArgs::from_slice(&data), Ok(args));
+
+    let args = IndexStateTypeArgs::new([1u8; 36], Some(info_type_code), Some([2u8; 32]));
+    let data = args.to_vec();
+    assert_eq!(data.len(), 101);
+    assert_eq!(IndexStateTypeArgs::from_slice(&data), Ok(args));
+    assert!(info_type_code.is_code_of(&[3u8; 32], 1));
+    assert!(!info_type_code.is_code_of(&[3u8; 32], 0));
+
+    // The owner lock hash requires the info type code
+    let data = IndexStateTypeArgs::new([1u8; 36], None, Some([2u8; 32])).to_vec();
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&data),
+        Err(Error::InvalidIndexStateTypeArgs)
+    );
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&data[..40]),
+        Err(Error::InvalidIndexStateTypeArgs)
+    );
+    assert_eq!(
+        IndexStateTypeArgs::from_slice(&[]),
+        Err(Error::InvalidIndexStateTypeArgs)
+    );
+}
+
+#[test]
+fn test_index_state_type_args_with_type_id_round_trip() {
+    // The Type ID depends on the index of the output
+    let id = type_id(&[1u8; 44], 0);
+    assert_ne!(id, type_id(&[1u8; 44], 1));
+
+    let args = IndexStateTypeArgs::new_type_id(id, None, None);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 32);
+    let parsed = IndexStateTypeArgs::from_slice(&data).unwrap();
+    assert_eq!(parsed.id, IndexStateId::TypeId(id));
+    assert_eq!(parsed.info_type_code, None);
+    assert_eq!(parsed.owner_lock_hash, None);
+
+    let info_type_code = InfoTypeCode::new([3u8; 32], 0);
+    let args = IndexStateTypeArgs::new_type_id(id, Some(info_type_code), None);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 65);
+    assert_eq!(IndexStateTypeArgs::from_slice(&data), Ok(args));
+
+    let args = IndexStateTypeArgs::new_type_id(id, Some(info_type_code), Some([2u8; 32]));
+    let data = args.to_vec();
+    assert_eq!(data.len(), 97);
+    assert_eq!(IndexStateTypeArgs::from_slice(&data), Ok(args));
+}
+
+#[test]
+fn test_time_lock_args_round_trip() {
+    let args = TimeLockArgs::new([1u8; 32], TimeKind::Timestamp, 1614829080456, [2u8; 32]);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 73);
+    assert_eq!(TimeLockArgs::from_slice(&data), Ok(args));
+    assert_eq!(
+        args.threshold_time(),
+        TimeValue::TimestampMillis(1614829080456)
+    );
+
+    // The owner lock hash is mandatory
+    assert_eq!(
+        TimeLockArgs::from_slice(&data[..41]),
+        Err(Error::InvalidTimeLockArgs)
+    );
+    let mut data = data;
+    data[32] = 3;
+    assert_eq!(
+        TimeLockArgs::from_slice(&data),
+        Err(Error::InvalidTimeInfoKind)
+    );
+
+    let mut args = args;
+    args.flags = TIME_LOCK_RING_MEDIAN_FLAG;
+    let data = args.to_vec();
+    assert_eq!(data.len(), 74);
+    let parsed = TimeLockArgs::from_slice(&data).unwrap();
+    assert_eq!(parsed, args);
+    assert!(parsed.has_ring_median());
+    let mut data = data;
+    data[73] = 2;
+    assert_eq!(
+        TimeLockArgs::from_slice(&data),
+        Err(Error::InvalidTimeLockArgs)
+    );
+}
+
+#[test]
+fn test_time_window_args_round_trip() {
+    let args = TimeWindowArgs::new([1u8; 32], TimeKind::BlockNumber, 1000, 2000);
+    let data = args.to_vec();
+    assert_eq!(data.len(), 49);
+    assert_eq!(TimeWindowArgs::from_slice(&data), Ok(args));
+    assert_eq!(args.start_time(), TimeValue::BlockNumber(1000));
+    assert_eq!(args.end_time(), TimeValue::BlockNumber(2000));
+
+    // The start can't be bigger than the end
+    let data = TimeWindowArgs::new([1u8; 32], TimeKind::BlockNumber, 2001, 2000).to_vec();
+    assert_eq!(
+        TimeWindowArgs::from_slice(&data),
+        Err(Error::InvalidTimeWindowArgs)
+    );
+    assert_eq!(
+        TimeWindowArgs::from_slice(&data[..48]),
+        Err(Error::InvalidTimeWindowArgs)
+    );
+}
+
+#[test]
+fn test_script_hash() {
+    let args = InfoTypeArgs::new([1u8; 32]).to_vec();
+    let script = Script::new_builder()
+        .code_hash([2u8; 32].pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(args.clone()).pack())
+        .build();
+    assert_eq!(
+        &script_hash(&[2u8; 32], ScriptHashType::Type as u8, &args)[..],
+        script.calc_script_hash().as_slice()
+    );
+}
+
+#[test]
+fn test_ring_time_from_infos() {
+    // The latest time info of the index 1 is a bad update which is far from the others
+    let times = [1000u64, 9_000_000, 3000, 4000, 5000];
+    let infos: Vec<TimeInfoData> = times
+        .iter()
+        .enumerate()
+        .map(|(index, time)| TimeInfoData::Versioned {
+            index: index as u8,
+            kind: TimeKind::Timestamp,
+            time: *time,
+        })
+        .collect();
+    let ring_time = RingTime::from_infos(&infos, &IndexStateData::new_versioned(1, 5)).unwrap();
+    assert_eq!(ring_time.latest, TimeValue::TimestampMillis(9_000_000));
+    assert_eq!(ring_time.min, TimeValue::TimestampMillis(1000));
+    assert_eq!(ring_time.max, TimeValue::TimestampMillis(9_000_000));
+    assert_eq!(ring_time.median, TimeValue::TimestampMillis(4000));
+    assert_eq!(ring_time.spread, 8_999_000);
+
+    // The lower median is used for the even sum, and the legacy timestamps are in milliseconds
+    let infos: Vec<TimeInfoData> = [40u32, 10, 30, 20]
+        .iter()
+        .enumerate()
+        .map(|(index, timestamp)| TimeInfoData::Timestamp {
+            index: index as u8,
+            timestamp: *timestamp,
+        })
+        .collect();
+    let ring_time = RingTime::from_infos(&infos, &IndexStateData::new(0, 4)).unwrap();
+    assert_eq!(ring_time.latest, TimeValue::TimestampMillis(40_000));
+    assert_eq!(ring_time.median, TimeValue::TimestampMillis(20_000));
+    assert_eq!(ring_time.spread, 30_000);
+}
+
+#[test]
+fn test_ring_time_error() {
+    let infos: Vec<TimeInfoData> = (0..3)
+        .map(|index| TimeInfoData::BlockNumber {
+            index,
+            block_number: 100 + index as u64,
+        })
+        .collect();
+    let index_state = IndexStateData::new(2, 3);
+    assert!(RingTime::from_infos(&infos, &index_state).is_ok());
+
+    // missing slot
+    assert_eq!(
+        RingTime::from_infos(&infos[..2], &index_state),
+        Err(Error::TimeInfoRingIncomplete)
+    );
+
+    // duplicated slot
+    let mut duplicated_infos = infos.clone();
+    duplicated_infos[0] = infos[1];
+    assert_eq!(
+        RingTime::from_infos(&duplicated_infos, &index_state),
+        Err(Error::TimeInfoRingIncomplete)
+    );
+
+    // different kinds
+    let mut mixed_infos = infos.clone();
+    mixed_infos[0] = TimeInfoData::Versioned {
+        index: 0,
+        kind: TimeKind::Timestamp,
+        time: 100,
+    };
+    assert_eq!(
+        RingTime::from_infos(&mixed_infos, &index_state),
+        Err(Error::TimeInfoKindNotSame)
+    );
+}
+
+#[test]
+fn test_rules_index_state() {
+    assert_eq!(
+        check_index_state(&IndexStateData::new_versioned(2, 3)),
+        Ok(())
+    );
+    assert_eq!(
+        check_index_state(&IndexStateData::new_versioned(0, 0)),
+        Err(RuleError::TimeInfoAmountError)
+    );
+    assert_eq!(
+        check_index_state(&IndexStateData::new_versioned(3, 3)),
+        Err(RuleError::TimeIndexOutOfBound)
+    );
+
+    let input_index_state = IndexStateData::new_versioned(2, 3);
+    assert_eq!(
+        check_index_state_updated(&input_index_state, &IndexStateData::new_versioned(0, 3)),
+        Ok(())
+    );
+    assert_eq!(
+        check_index_state_updated(&input_index_state, &IndexStateData::new_versioned(1, 3)),
+        Err(RuleError::TimeIndexIncreaseError)
+    );
+    assert_eq!(
+        check_index_state_updated(&input_index_state, &IndexStateData::new(0, 3)),
+        Err(RuleError::IndexStateVersionDowngrade)
+    );
+    assert_eq!(
+        check_index_state_migrated(&input_index_state, &IndexStateData::new_versioned(2, 5)),
+        Ok(())
+    );
+    assert_eq!(
+        check_index_state_migrated(&input_index_state, &IndexStateData::new_versioned(0, 5)),
+        Err(RuleError::TimeIndexChangedInMigration)
+    );
+    assert_eq!(
+        check_index_state_migrated(&input_index_state, &IndexStateData::new_versioned(0, 0)),
+        Err(RuleError::TimeInfoAmountError)
+    );
+}
+
+#[test]
+fn test_rules_info_slots() {
+    assert_eq!(check_info_slots(vec![2, 0, 1], 0, 3), Ok(()));
+    assert_eq!(check_info_slots(vec![3, 4], 3, 5), Ok(()));
+    assert_eq!(
+        check_info_slots(vec![0, 1, 3], 0, 3),
+        Err(RuleError::TimeInfoIndexOutOfBound)
+    );
+    assert_eq!(
+        check_info_slots(vec![0, 1, 1], 0, 3),
+        Err(RuleError::TimeInfoSlotDuplicated)
+    );
+    assert_eq!(
+        check_info_slots(vec![0, 2], 0, 3),
+        Err(RuleError::TimeInfoSlotMissing)
+    );
+}
+
+#[test]
+fn test_rules_info_updated() {
+    let output_index_state = IndexStateData::new_versioned(1, 3);
+    let input_info = TimeInfoData::Timestamp {
+        index: 1,
+        timestamp: 1000,
+    };
+    let output_info = TimeInfoData::Versioned {
+        index: 1,
+        kind: TimeKind::Timestamp,
+        time: 2_000_000,
+    };
+    assert_eq!(
+        check_info_updated(&input_info, &output_info, &output_index_state),
+        Ok(())
+    );
+    assert_eq!(
+        check_info_updated(&output_info, &input_info, &output_index_state),
+        Err(RuleError::TimeInfoVersionDowngrade)
+    );
+    // The legacy layouts of the same version have different lengths
+    let block_number_info = TimeInfoData::BlockNumber {
+        index: 1,
+        block_number: 1000,
+    };
+    assert_eq!(
+        check_info_updated(&input_info, &block_number_info, &output_index_state),
+        Err(RuleError::TimeInfoDataLenError)
+    );
+    assert_eq!(
+        check_info_updated(
+            &input_info,
+            &output_info,
+            &IndexStateData::new_versioned(1, 1)
+        ),
+        Err(RuleError::TimeInfoIndexOutOfBound)
+    );
+    assert_eq!(
+        check_info_updated(
+            &input_info,
+            &output_info,
+            &IndexStateData::new_versioned(2, 3)
+        ),
+        Err(RuleError::TimeInfoIndexNotSame)
+    );
+    let other_slot_info = TimeInfoData::Timestamp {
+        index: 2,
+        timestamp: 1000,
+    };
+    assert_eq!(
+        check_info_updated(&other_slot_info, &output_info, &output_index_state),
+        Err(RuleError::TimeInfoSlotChanged)
+    );
+}
+
+#[test]
+fn test_rules_info_time() {
+    let info_type_args = InfoTypeArgs {
+        min_step: 1000,
+        max_step: 60_000,
+        ..InfoTypeArgs::new([0u8; 32])
+    };
+    let latest_info = TimeInfoData::Timestamp {
+        index: 0,
+        timestamp: 1000,
+    };
+    let build_output_info = |time| TimeInfoData::Versioned {
+        index: 1,
+        kind: TimeKind::Timestamp,
+        time,
+    };
+
+    // The legacy timestamp in seconds is compared in milliseconds
+    assert_eq!(
+        check_info_time(&build_output_info(1_030_000), &latest_info, &info_type_args),
+        Ok(())
+    );
+    assert_eq!(
+        check_info_time(&build_output_info(1_000_000), &latest_info, &info_type_args),
+        Err(RuleError::TimeNotBigger)
+    );
+    assert_eq!(
+        check_info_time(&build_output_info(1_000_500), &latest_info, &info_type_args),
+        Err(RuleError::TimeInfoStepTooSmall)
+    );
+    assert_eq!(
+        check_info_time(&build_output_info(1_060_001), &latest_info, &info_type_args),
+        Err(RuleError::TimeInfoStepTooLarge)
+    );
+    let block_number_info = TimeInfoData::Versioned {
+        index: 1,
+        kind: TimeKind::BlockNumber,
+        time: 2000,
+    };
+    assert_eq!(
+        check_info_time(&block_number_info, &latest_info, &info_type_args),
+        Err(RuleError::TimeInfoKindNotSame)
+    );
+
+    // The steps are in milliseconds before the migration too
+    let build_legacy_info = |timestamp| TimeInfoData::Timestamp {
+        index: 1,
+        timestamp,
+    };
+    assert_eq!(
+        check_info_time(&build_legacy_info(1030), &latest_info, &info_type_args),
+        Ok(())
+    );
+    assert_eq!(
+        check_info_time(&build_legacy_info(1061), &latest_info, &info_type_args),
+        Err(RuleError::TimeInfoStepTooLarge)
+    );
+}
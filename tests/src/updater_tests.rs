@@ -0,0 +1,488 @@
+use super::util::{
+    build_funding_output, deploy_oracle, new_genesis_tx, CKB, FEE, GENESIS_TIMESTAMP,
+    SUM_OF_TIME_INFO_CELLS,
+};
+use ckb_testtool::context::Context;
+use ckb_tool::ckb_types::{
+    bytes::Bytes,
+    core::{HeaderBuilder, HeaderView, TransactionView},
+    packed::*,
+    prelude::*,
+};
+use time_cell_types::{
+    IndexStateData, InfoTypeArgs, TimeInfoData, TimeKind, HEADER_DEP_PROOF_FLAG, MULTI_SIG_FLAG,
+};
+use time_oracle_sdk::LiveCell;
+use time_oracle_updater::{ChainClient, Error, TxSigner, Updater, UpdaterConfig};
+
+const MILLIS_PER_BLOCK: u64 = 8000;
+const UPDATE_INTERVAL: u64 = 10;
+const MAX_CYCLES: u64 = 10_000_000;
+
+// The in-memory chain whose transactions are verified by the context, and the outputs of the
+// verified transactions become the live cells
+struct MockChain {
+    context: Context,
+    live_cells: Vec<LiveCell>,
+    // The header of the block number i
+    headers: Vec<HeaderView>,
+    sent_txs: Vec<TransactionView>,
+}
+
+impl MockChain {
+    fn new(context: Context) -> Self {
+        let mut chain = MockChain {
+            context,
+            live_cells: vec![],
+            headers: vec![],
+            sent_txs: vec![],
+        };
+        chain.produce_blocks(100);
+        chain
+    }
+
+    fn tip(&self) -> HeaderView {
+        self.headers.last().cloned().expect("tip")
+    }
+
+    // The timestamp of the block is MILLIS_PER_BLOCK after the last block
+    fn produce_blocks(&mut self, count: u64) {
+        for _ in 0..count {
+            let number = self.headers.len() as u64;
+            let header = HeaderBuilder::default()
+                .number(number.pack())
+                .timestamp((GENESIS_TIMESTAMP + number * MILLIS_PER_BLOCK).pack())
+                .build();
+            self.context.insert_header(header.clone());
+            self.headers.push(header);
+        }
+    }
+
+    fn create_live_cell(&mut self, output: CellOutput, data: Bytes) -> LiveCell {
+        let out_point = self.context.create_cell(output.clone(), data.clone());
+        let live_cell = LiveCell {
+            out_point,
+            output,
+            data,
+        };
+        self.live_cells.push(live_cell.clone());
+        live_cell
+    }
+}
+
+impl ChainClient for MockChain {
+    fn tip_header(&mut self) -> Result<HeaderView, Error> {
+        Ok(self.tip())
+    }
+
+    // The median time is a little behind the tip
+    fn median_time(&mut self) -> Result<u64, Error> {
+        Ok(self.tip().timestamp() - MILLIS_PER_BLOCK)
+    }
+
+    fn live_cells(&mut self, type_script: &Script) -> Result<Vec<LiveCell>, Error> {
+        Ok(self
+            .live_cells
+            .iter()
+            .filter(|cell| match cell.output.type_().to_opt() {
+                Some(cell_type) => cell_type.as_slice() == type_script.as_slice(),
+                None => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn wallet_cells(&mut self, lock_script: &Script) -> Result<Vec<LiveCell>, Error> {
+        Ok(self
+            .live_cells
+            .iter()
+            .filter(|cell| {
+                cell.output.lock().as_slice() == lock_script.as_slice()
+                    && cell.output.type_().is_none()
+                    && cell.data.is_empty()
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn header_by_number(&mut self, number: u64) -> Result<HeaderView, Error> {
+        self.headers
+            .get(number as usize)
+            .cloned()
+            .ok_or_else(|| Error::Client(format!("header {} not found", number)))
+    }
+
+    fn send_transaction(&mut self, tx: TransactionView) -> Result<Byte32, Error> {
+        self.context
+            .verify_tx(&tx, MAX_CYCLES)
+            .map_err(|err| Error::Client(err.to_string()))?;
+        let input_out_points = tx.input_pts_iter().collect::<Vec<_>>();
+        self.live_cells.retain(|cell| {
+            !input_out_points
+                .iter()
+                .any(|out_point| out_point.as_slice() == cell.out_point.as_slice())
+        });
+        for ((output, data), out_point) in tx.outputs_with_data_iter().zip(tx.output_pts()) {
+            self.context.create_cell_with_out_point(
+                out_point.clone(),
+                output.clone(),
+                data.clone(),
+            );
+            self.live_cells.push(LiveCell {
+                out_point,
+                output,
+                data,
+            });
+        }
+        self.sent_txs.push(tx.clone());
+        Ok(tx.hash())
+    }
+}
+
+// The oracle cells are locked by the always success lock in the tests
+struct AlwaysSuccessSigner;
+
+impl TxSigner for AlwaysSuccessSigner {
+    fn sign(&self, tx: TransactionView, _inputs: &[LiveCell]) -> Result<TransactionView, Error> {
+        Ok(tx)
+    }
+}
+
+// Deploy the oracle and send the genesis transaction to the mock chain, and the time info of the
+// slot i is built by build_info
+fn create_updater<F>(
+    build_info: F,
+    info_type_args: InfoTypeArgs,
+) -> Updater<MockChain, AlwaysSuccessSigner>
+where
+    F: Fn(u8) -> TimeInfoData,
+{
+    create_updater_with_wallet(build_info, info_type_args, &[1000 * CKB], |_| vec![])
+}
+
+// The wallet cells of the capacities are locked by the always success lock with the wallet args,
+// and the updaters of the time index state cell are built from the wallet lock
+fn create_updater_with_wallet<F, U>(
+    build_info: F,
+    info_type_args: InfoTypeArgs,
+    wallet_capacities: &[u64],
+    build_updaters: U,
+) -> Updater<MockChain, AlwaysSuccessSigner>
+where
+    F: Fn(u8) -> TimeInfoData,
+    U: Fn(&Script) -> Vec<[u8; 32]>,
+{
+    let mut context = Context::default();
+    let oracle = deploy_oracle(&mut context);
+    let mut chain = MockChain::new(context);
+    let funding_cell = chain.create_live_cell(build_funding_output(&oracle), Bytes::new());
+    let wallet_lock = oracle
+        .lock_script
+        .clone()
+        .as_builder()
+        .args(Bytes::from(&b"wallet"[..]).pack())
+        .build();
+    for capacity in wallet_capacities {
+        let wallet_output = CellOutput::new_builder()
+            .capacity(capacity.pack())
+            .lock(wallet_lock.clone())
+            .build();
+        chain.create_live_cell(wallet_output, Bytes::new());
+    }
+    let mut genesis_tx = new_genesis_tx(&oracle, funding_cell, info_type_args, build_info);
+    // The version 2 of the time index state cell data can't have an empty updater list
+    let updaters = build_updaters(&wallet_lock);
+    if !updaters.is_empty() {
+        genesis_tx.index_state =
+            IndexStateData::new_with_updaters(0, SUM_OF_TIME_INFO_CELLS, updaters);
+    }
+    let genesis_tx = genesis_tx.build().expect("genesis tx");
+    chain
+        .send_transaction(genesis_tx.clone())
+        .expect("send genesis tx");
+
+    let type_script_of = |position: usize| {
+        genesis_tx
+            .outputs()
+            .get(position)
+            .and_then(|output| output.type_().to_opt())
+            .expect("type script")
+    };
+    let config = UpdaterConfig {
+        codes: oracle.codes,
+        lock_deps: vec![oracle.lock_script_dep],
+        index_state_type_script: type_script_of(0),
+        info_type_script: type_script_of(1),
+        wallet_lock,
+        update_interval: UPDATE_INTERVAL,
+        fee: FEE,
+    };
+    Updater::new(chain, AlwaysSuccessSigner, config)
+}
+
+fn build_timestamp_info(index: u8) -> TimeInfoData {
+    TimeInfoData::Versioned {
+        index,
+        kind: TimeKind::Timestamp,
+        time: GENESIS_TIMESTAMP + index as u64,
+    }
+}
+
+fn lock_hash_of(lock: &Script) -> [u8; 32] {
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(lock.calc_script_hash().as_slice());
+    lock_hash
+}
+
+// The latest time info of the oracle on the mock chain
+fn load_latest_info(updater: &mut Updater<MockChain, AlwaysSuccessSigner>) -> TimeInfoData {
+    let config = updater.config().clone();
+    let chain = updater.client_mut();
+    let index_state_cells = chain
+        .live_cells(&config.index_state_type_script)
+        .expect("index state cell");
+    let index_state = IndexStateData::from_slice(&index_state_cells[0].data).expect("index state");
+    chain
+        .live_cells(&config.info_type_script)
+        .expect("info cells")
+        .iter()
+        .filter_map(|cell| TimeInfoData::from_slice(&cell.data).ok())
+        .find(|info| info.index() == index_state.index)
+        .expect("latest info")
+}
+
+#[test]
+fn test_updater_rotates_timestamp_oracle() {
+    let mut updater = create_updater(build_timestamp_info, InfoTypeArgs::new([0u8; 32]));
+
+    updater.poll().expect("poll").expect("update tx");
+    let median_time = updater.client_mut().median_time().expect("median time");
+    assert_eq!(
+        load_latest_info(&mut updater),
+        TimeInfoData::Versioned {
+            index: 1,
+            kind: TimeKind::Timestamp,
+            time: median_time,
+        }
+    );
+
+    // The oracle is not updated until UPDATE_INTERVAL blocks are produced
+    updater.client_mut().produce_blocks(UPDATE_INTERVAL - 1);
+    assert!(updater.poll().expect("poll").is_none());
+
+    updater.client_mut().produce_blocks(1);
+    updater.poll().expect("poll").expect("update tx");
+    assert_eq!(load_latest_info(&mut updater).index(), 2);
+    assert_eq!(updater.client().sent_txs.len(), 3);
+}
+
+#[test]
+fn test_updater_rotates_block_number_oracle() {
+    let build_block_number_info = |index| TimeInfoData::BlockNumber {
+        index,
+        block_number: index as u64,
+    };
+    let mut updater = create_updater(build_block_number_info, InfoTypeArgs::new([0u8; 32]));
+
+    // The ring goes around and the time info cell of the slot 0 is overwritten again
+    for count in 1..=SUM_OF_TIME_INFO_CELLS as u64 + 1 {
+        updater.client_mut().produce_blocks(UPDATE_INTERVAL);
+        updater.poll().expect("poll").expect("update tx");
+        let tip_number = updater.client_mut().tip_header().expect("tip").number();
+        assert_eq!(
+            load_latest_info(&mut updater),
+            TimeInfoData::BlockNumber {
+                index: (count % SUM_OF_TIME_INFO_CELLS as u64) as u8,
+                block_number: tip_number,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_updater_rotates_oracle_with_header_dep() {
+    let mut info_type_args = InfoTypeArgs::new([0u8; 32]);
+    info_type_args.flags |= HEADER_DEP_PROOF_FLAG;
+    let mut updater = create_updater(build_timestamp_info, info_type_args);
+
+    updater.poll().expect("poll").expect("update tx");
+    let tip = updater.client_mut().tip_header().expect("tip");
+    let update_tx = updater.client().sent_txs.last().expect("update tx").clone();
+    assert_eq!(
+        update_tx
+            .header_deps()
+            .get(0)
+            .expect("header dep")
+            .as_slice(),
+        tip.hash().as_slice()
+    );
+    assert_eq!(
+        load_latest_info(&mut updater),
+        TimeInfoData::Versioned {
+            index: 1,
+            kind: TimeKind::Timestamp,
+            time: tip.timestamp(),
+        }
+    );
+}
+
+#[test]
+fn test_updater_waits_for_time_bigger_than_latest() {
+    // The latest time of the slot 0 is later than the median time of the tip
+    let build_future_info = |index| TimeInfoData::Versioned {
+        index,
+        kind: TimeKind::Timestamp,
+        time: GENESIS_TIMESTAMP * 2 + index as u64,
+    };
+    let mut updater = create_updater(build_future_info, InfoTypeArgs::new([0u8; 32]));
+
+    assert!(updater.poll().expect("poll").is_none());
+    assert_eq!(updater.client().sent_txs.len(), 1);
+}
+
+#[test]
+fn test_updater_pays_fee_from_wallet_cells() {
+    // The first wallet cell can't pay the fee and the occupied capacity of the change
+    let mut updater = create_updater_with_wallet(
+        build_timestamp_info,
+        InfoTypeArgs::new([0u8; 32]),
+        &[30 * CKB, 30 * CKB],
+        |_| vec![],
+    );
+
+    updater.poll().expect("poll").expect("update tx");
+    let update_tx = updater.client().sent_txs.last().expect("update tx").clone();
+    assert_eq!(update_tx.inputs().len(), 4);
+    let change_output = update_tx.outputs().get(2).expect("change output");
+    assert_eq!(
+        change_output.lock().as_slice(),
+        updater.config().wallet_lock.as_slice()
+    );
+    let change_capacity: u64 = change_output.capacity().unpack();
+    assert_eq!(change_capacity, 60 * CKB - FEE);
+
+    // The change is the wallet cell of the next update
+    updater.client_mut().produce_blocks(UPDATE_INTERVAL);
+    updater.poll().expect("poll").expect("update tx");
+    assert_eq!(load_latest_info(&mut updater).index(), 2);
+}
+
+#[test]
+fn test_updater_wallet_cell_not_found() {
+    let mut updater = create_updater_with_wallet(
+        build_timestamp_info,
+        InfoTypeArgs::new([0u8; 32]),
+        &[],
+        |_| vec![],
+    );
+
+    assert_eq!(updater.poll(), Err(Error::WalletCellNotFound));
+}
+
+#[test]
+fn test_updater_steps_timestamp_oracle_after_outage() {
+    // The median time is 98 blocks after the latest time of the genesis
+    let max_step = 40 * MILLIS_PER_BLOCK;
+    let mut info_type_args = InfoTypeArgs::new([0u8; 32]);
+    info_type_args.max_step = max_step;
+    let mut updater = create_updater(build_timestamp_info, info_type_args);
+
+    for count in 1..=2 {
+        updater.poll().expect("poll").expect("update tx");
+        assert_eq!(
+            load_latest_info(&mut updater),
+            TimeInfoData::Versioned {
+                index: count,
+                kind: TimeKind::Timestamp,
+                time: GENESIS_TIMESTAMP + count as u64 * max_step,
+            }
+        );
+        updater.client_mut().produce_blocks(UPDATE_INTERVAL);
+    }
+
+    // The oracle catches up with the median time of the tip
+    updater.poll().expect("poll").expect("update tx");
+    let median_time = updater.client_mut().median_time().expect("median time");
+    assert_eq!(
+        load_latest_info(&mut updater),
+        TimeInfoData::Versioned {
+            index: 3,
+            kind: TimeKind::Timestamp,
+            time: median_time,
+        }
+    );
+}
+
+#[test]
+fn test_updater_steps_oracle_with_header_dep_after_outage() {
+    let build_block_number_info = |index| TimeInfoData::BlockNumber {
+        index,
+        block_number: index as u64,
+    };
+    let mut info_type_args = InfoTypeArgs::new([0u8; 32]);
+    info_type_args.flags |= HEADER_DEP_PROOF_FLAG;
+    info_type_args.max_step = 20;
+    let mut updater = create_updater(build_block_number_info, info_type_args);
+
+    // The header dep is the header of max_step after the latest block number rather than the tip
+    updater.poll().expect("poll").expect("update tx");
+    let header = updater.client_mut().header_by_number(20).expect("header");
+    let update_tx = updater.client().sent_txs.last().expect("update tx").clone();
+    assert_eq!(
+        update_tx
+            .header_deps()
+            .get(0)
+            .expect("header dep")
+            .as_slice(),
+        header.hash().as_slice()
+    );
+    assert_eq!(
+        load_latest_info(&mut updater),
+        TimeInfoData::BlockNumber {
+            index: 1,
+            block_number: 20,
+        }
+    );
+}
+
+#[test]
+fn test_updater_rejects_multi_sig_oracle() {
+    let mut info_type_args = InfoTypeArgs::new([0u8; 32]);
+    info_type_args.flags |= MULTI_SIG_FLAG;
+    info_type_args.threshold = 1;
+    info_type_args.operators = vec![[1u8; 20]];
+    let mut updater = create_updater(build_timestamp_info, info_type_args);
+
+    assert_eq!(updater.check_oracle(), Err(Error::MultiSigNotSupported));
+    assert_eq!(updater.poll(), Err(Error::MultiSigNotSupported));
+    assert_eq!(updater.client().sent_txs.len(), 1);
+}
+
+#[test]
+fn test_updater_authorized_by_wallet_lock() {
+    // The oracle lock isn't an updater, and the wallet input authorizes the update
+    let mut updater = create_updater_with_wallet(
+        build_timestamp_info,
+        InfoTypeArgs::new([0u8; 32]),
+        &[1000 * CKB],
+        |wallet_lock| vec![lock_hash_of(wallet_lock)],
+    );
+
+    updater.check_oracle().expect("check oracle");
+    updater.poll().expect("poll").expect("update tx");
+    assert_eq!(load_latest_info(&mut updater).index(), 1);
+}
+
+#[test]
+fn test_updater_rejects_oracle_of_other_updaters() {
+    let mut updater = create_updater_with_wallet(
+        build_timestamp_info,
+        InfoTypeArgs::new([0u8; 32]),
+        &[1000 * CKB],
+        |_| vec![[1u8; 32]],
+    );
+
+    assert_eq!(updater.check_oracle(), Err(Error::UpdaterNotAuthorized));
+    assert_eq!(updater.poll(), Err(Error::UpdaterNotAuthorized));
+    assert_eq!(updater.client().sent_txs.len(), 1);
+}
@@ -0,0 +1,316 @@
+use super::*;
+use ckb_testtool::{builtin::ALWAYS_SUCCESS, context::Context};
+use ckb_tool::ckb_types::{
+    bytes::Bytes,
+    core::{ScriptHashType, TransactionBuilder, TransactionView},
+    packed::*,
+    prelude::*,
+};
+use time_cell_types::{IndexStateData, InfoTypeArgs, TimeInfoData, TimeKind};
+use time_oracle_sdk::{GenesisTx, LiveCell, OracleCodes, ScriptCode};
+
+// The ring size of the oracles of the tests
+pub const SUM_OF_TIME_INFO_CELLS: u8 = 12;
+
+// The oracle built by the SDK
+pub const GENESIS_TIMESTAMP: u64 = 1614829080456;
+pub const CKB: u64 = 100_000_000;
+pub const FEE: u64 = 1000;
+
+// The oracle read by the consumer scripts
+pub const LATEST_INDEX: u8 = 5;
+pub const LATEST_TIMESTAMP: u64 = 1614829080456;
+pub const LATEST_BLOCK_NUMBER: u64 = 10000;
+
+// The age of the slot in the updates, and the time info cell of the latest index has the
+// latest time
+fn slot_age(index: u8) -> u8 {
+    (LATEST_INDEX + SUM_OF_TIME_INFO_CELLS - index) % SUM_OF_TIME_INFO_CELLS
+}
+
+// Every older slot is one step earlier than the next one
+pub fn build_timestamp_info_cell_data(index: u8) -> Bytes {
+    Bytes::from(
+        TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::Timestamp,
+            time: LATEST_TIMESTAMP - slot_age(index) as u64 * 60_000,
+        }
+        .to_vec(),
+    )
+}
+
+pub fn build_legacy_timestamp_info_cell_data(index: u8) -> Bytes {
+    Bytes::from(
+        TimeInfoData::Timestamp {
+            index,
+            timestamp: (LATEST_TIMESTAMP / 1000) as u32 - slot_age(index) as u32 * 60,
+        }
+        .to_vec(),
+    )
+}
+
+pub fn build_block_number_info_cell_data(index: u8) -> Bytes {
+    Bytes::from(
+        TimeInfoData::BlockNumber {
+            index,
+            block_number: LATEST_BLOCK_NUMBER - slot_age(index) as u64 * 10,
+        }
+        .to_vec(),
+    )
+}
+
+// The lock hash of the cells which are locked by the always success script of the contexts
+pub fn always_success_lock_hash() -> [u8; 32] {
+    let lock_script = Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS))
+        .hash_type(ScriptHashType::Data.into())
+        .build();
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(lock_script.calc_script_hash().as_slice());
+    lock_hash
+}
+
+// The deployed consumer script and the oracle cells of the consumer transactions
+pub struct ConsumerContext {
+    pub lock_script: Script,
+    pub lock_script_dep: CellDep,
+    pub consumer_out_point: OutPoint,
+    pub consumer_script_dep: CellDep,
+    pub info_type_hash: [u8; 32],
+    // The time index state cell dep is the first, and the time info cell deps of all the slots
+    // follow it
+    pub oracle_deps: Vec<CellDep>,
+}
+
+impl ConsumerContext {
+    // The inputs and outputs have the empty data and witnesses
+    pub fn build_tx(&self, inputs: Vec<CellInput>, outputs: Vec<CellOutput>) -> TransactionView {
+        let outputs_data = vec![Bytes::new(); outputs.len()];
+        let witnesses = vec![Bytes::new(); inputs.len()];
+        TransactionBuilder::default()
+            .inputs(inputs)
+            .outputs(outputs)
+            .outputs_data(outputs_data.pack())
+            .cell_dep(self.lock_script_dep.clone())
+            .cell_dep(self.consumer_script_dep.clone())
+            .cell_deps(self.oracle_deps.clone())
+            .witnesses(witnesses.pack())
+            .build()
+    }
+}
+
+// Deploy the oracle contracts and the consumer contract of consumer_name, and create the time
+// index state cell and the time info cells of the oracle, whose data are built by
+// build_info_data
+pub fn create_consumer_context<F>(
+    context: &mut Context,
+    consumer_name: &str,
+    build_info_data: F,
+) -> ConsumerContext
+where
+    F: Fn(u8) -> Bytes,
+{
+    // deploy contract
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    let info_bin: Bytes = Loader::default().load_binary("info-type");
+    let info_out_point = context.deploy_cell(info_bin);
+
+    let consumer_bin: Bytes = Loader::default().load_binary(consumer_name);
+    let consumer_out_point = context.deploy_cell(consumer_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point)
+        .build();
+
+    // prepare the oracle cells
+    let index_state_type_script = context
+        .build_script(&index_state_out_point, Bytes::from(vec![0u8; 36]))
+        .expect("script");
+    let mut index_state_type_hash = [0u8; 32];
+    index_state_type_hash.copy_from_slice(index_state_type_script.calc_script_hash().as_slice());
+    let info_args = Bytes::from(InfoTypeArgs::new(index_state_type_hash).to_vec());
+    let info_type_script = context
+        .build_script(&info_out_point, info_args)
+        .expect("script");
+    let mut info_type_hash = [0u8; 32];
+    info_type_hash.copy_from_slice(info_type_script.calc_script_hash().as_slice());
+
+    let index_state_data = IndexStateData::new_versioned(LATEST_INDEX, SUM_OF_TIME_INFO_CELLS);
+    let mut oracle_out_points = vec![context.create_cell(
+        CellOutput::new_builder()
+            .capacity(500u64.pack())
+            .lock(lock_script.clone())
+            .type_(Some(index_state_type_script).pack())
+            .build(),
+        Bytes::from(index_state_data.to_vec()),
+    )];
+    for index in 0..SUM_OF_TIME_INFO_CELLS {
+        oracle_out_points.push(
+            context.create_cell(
+                CellOutput::new_builder()
+                    .capacity(500u64.pack())
+                    .lock(lock_script.clone())
+                    .type_(Some(info_type_script.clone()).pack())
+                    .build(),
+                build_info_data(index),
+            ),
+        );
+    }
+    let oracle_deps = oracle_out_points
+        .into_iter()
+        .map(|out_point| CellDep::new_builder().out_point(out_point).build())
+        .collect();
+
+    ConsumerContext {
+        lock_script,
+        lock_script_dep,
+        consumer_script_dep: CellDep::new_builder()
+            .out_point(consumer_out_point.clone())
+            .build(),
+        consumer_out_point,
+        info_type_hash,
+        oracle_deps,
+    }
+}
+
+// The deployed codes of the oracle, and the oracle cells are locked by the always success lock
+pub struct Oracle {
+    pub codes: OracleCodes,
+    pub lock_script: Script,
+    pub lock_script_dep: CellDep,
+}
+
+pub fn build_script_code(context: &mut Context, out_point: OutPoint) -> ScriptCode {
+    let script = context
+        .build_script(&out_point, Bytes::new())
+        .expect("script");
+    ScriptCode {
+        code_hash: script.code_hash(),
+        hash_type: ScriptHashType::Data,
+        cell_dep: CellDep::new_builder().out_point(out_point).build(),
+    }
+}
+
+pub fn deploy_oracle(context: &mut Context) -> Oracle {
+    let index_state_bin: Bytes = Loader::default().load_binary("index-state-type");
+    let index_state_out_point = context.deploy_cell(index_state_bin);
+
+    let info_bin: Bytes = Loader::default().load_binary("info-type");
+    let info_out_point = context.deploy_cell(info_bin);
+
+    // deploy always_success script
+    let always_success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+    let lock_script = context
+        .build_script(&always_success_out_point, Default::default())
+        .expect("script");
+    let lock_script_dep = CellDep::new_builder()
+        .out_point(always_success_out_point)
+        .build();
+
+    Oracle {
+        codes: OracleCodes {
+            index_state_type: build_script_code(context, index_state_out_point),
+            info_type: build_script_code(context, info_out_point),
+        },
+        lock_script,
+        lock_script_dep,
+    }
+}
+
+// The funding cell which pays for the genesis transaction
+pub fn build_funding_output(oracle: &Oracle) -> CellOutput {
+    CellOutput::new_builder()
+        .capacity((1_000_000 * CKB).pack())
+        .lock(oracle.lock_script.clone())
+        .build()
+}
+
+// The funding cell which pays for the genesis transaction or the fee of the update transaction
+pub fn create_funding_cell(context: &mut Context, oracle: &Oracle) -> LiveCell {
+    let funding_output = build_funding_output(oracle);
+    let funding_out_point = context.create_cell(funding_output.clone(), Bytes::new());
+    LiveCell {
+        out_point: funding_out_point,
+        output: funding_output,
+        data: Bytes::new(),
+    }
+}
+
+// The genesis transaction without an owner, and the time info of the slot i is built by
+// build_info
+pub fn new_genesis_tx<F>(
+    oracle: &Oracle,
+    funding_cell: LiveCell,
+    info_type_args: InfoTypeArgs,
+    build_info: F,
+) -> GenesisTx
+where
+    F: Fn(u8) -> TimeInfoData,
+{
+    GenesisTx {
+        codes: oracle.codes.clone(),
+        lock_deps: vec![oracle.lock_script_dep.clone()],
+        funding_cells: vec![funding_cell],
+        oracle_lock: oracle.lock_script.clone(),
+        owner_lock_hash: None,
+        type_id: false,
+        index_state: IndexStateData::new_versioned(0, SUM_OF_TIME_INFO_CELLS),
+        info_type_args,
+        infos: (0..SUM_OF_TIME_INFO_CELLS).map(build_info).collect(),
+        change_lock: oracle.lock_script.clone(),
+        fee: FEE,
+    }
+}
+
+// The time info of the slot i is GENESIS_TIMESTAMP + i seconds
+pub fn new_timestamp_genesis_tx(context: &mut Context, oracle: &Oracle) -> GenesisTx {
+    let funding_cell = create_funding_cell(context, oracle);
+    new_genesis_tx(
+        oracle,
+        funding_cell,
+        InfoTypeArgs::new([0u8; 32]),
+        |index| TimeInfoData::Versioned {
+            index,
+            kind: TimeKind::Timestamp,
+            time: GENESIS_TIMESTAMP + index as u64 * 1000,
+        },
+    )
+}
+
+pub fn build_genesis_tx(
+    context: &mut Context,
+    oracle: &Oracle,
+    owner_lock_hash: Option<[u8; 32]>,
+) -> TransactionView {
+    GenesisTx {
+        owner_lock_hash,
+        ..new_timestamp_genesis_tx(context, oracle)
+    }
+    .build()
+    .expect("genesis tx")
+}
+
+// Put the outputs of the transaction into the context as the live cells
+pub fn commit_tx(context: &mut Context, tx: &TransactionView) -> Vec<LiveCell> {
+    tx.outputs_with_data_iter()
+        .zip(tx.output_pts())
+        .map(|((output, data), out_point)| {
+            context.create_cell_with_out_point(out_point.clone(), output.clone(), data.clone());
+            LiveCell {
+                out_point,
+                output,
+                data,
+            }
+        })
+        .collect()
+}
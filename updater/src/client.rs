@@ -0,0 +1,200 @@
+use crate::error::Error;
+use ckb_jsonrpc_types::{
+    BlockNumber, CellOutput as JsonCellOutput, HeaderView as JsonHeaderView, JsonBytes,
+    OutPoint as JsonOutPoint, Script as JsonScript, Transaction as JsonTransaction, Uint64,
+};
+use ckb_types::{
+    core::{HeaderView, TransactionView},
+    packed::{Byte32, CellOutput, OutPoint, Script},
+    prelude::*,
+    H256,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use time_oracle_sdk::LiveCell;
+
+const GET_CELLS_PAGE_SIZE: u64 = 100;
+
+/// The chain source of the updater, which can be a CKB node with the indexer, or a mock chain in
+/// the tests
+pub trait ChainClient {
+    fn tip_header(&mut self) -> Result<HeaderView, Error>;
+
+    /// The median of the timestamps of the blocks before the tip in milliseconds, which is the
+    /// time of the since of the timestamp
+    fn median_time(&mut self) -> Result<u64, Error>;
+
+    /// The live cells whose type script is equal to the type script
+    fn live_cells(&mut self, type_script: &Script) -> Result<Vec<LiveCell>, Error>;
+
+    /// The live cells without type script and data whose lock script is equal to the lock script,
+    /// which pay the fee of the update transaction
+    fn wallet_cells(&mut self, lock_script: &Script) -> Result<Vec<LiveCell>, Error>;
+
+    /// The header of the main chain with the block number
+    fn header_by_number(&mut self, number: u64) -> Result<HeaderView, Error>;
+
+    /// Send the signed transaction and return the transaction hash
+    fn send_transaction(&mut self, tx: TransactionView) -> Result<Byte32, Error>;
+}
+
+/// The chain client of the json rpc of the CKB node and the CKB indexer
+pub struct RpcClient {
+    ckb_url: String,
+    indexer_url: String,
+    http: reqwest::blocking::Client,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct ChainInfo {
+    median_time: Uint64,
+}
+
+#[derive(Deserialize)]
+struct IndexerCell {
+    output: JsonCellOutput,
+    output_data: JsonBytes,
+    out_point: JsonOutPoint,
+}
+
+#[derive(Deserialize)]
+struct IndexerCells {
+    objects: Vec<IndexerCell>,
+    last_cursor: JsonBytes,
+}
+
+impl RpcClient {
+    pub fn new(ckb_url: &str, indexer_url: &str) -> Self {
+        RpcClient {
+            ckb_url: ckb_url.to_string(),
+            indexer_url: indexer_url.to_string(),
+            http: reqwest::blocking::Client::new(),
+            id: 0,
+        }
+    }
+
+    fn call<T: DeserializeOwned>(
+        &mut self,
+        is_indexer: bool,
+        method: &str,
+        params: Value,
+    ) -> Result<T, Error> {
+        self.id += 1;
+        let url = if is_indexer {
+            &self.indexer_url
+        } else {
+            &self.ckb_url
+        };
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.id,
+            "method": method,
+            "params": params,
+        });
+        let response: RpcResponse = self
+            .http
+            .post(url)
+            .json(&request)
+            .send()
+            .and_then(|response| response.json())
+            .map_err(|err| Error::Client(err.to_string()))?;
+        if let Some(err) = response.error {
+            return Err(Error::Client(err.to_string()));
+        }
+        serde_json::from_value(response.result.unwrap_or(Value::Null))
+            .map_err(|err| Error::Client(err.to_string()))
+    }
+
+    // The indexer searches the script by the prefix of the args, so the cells are filtered again
+    fn search_cells<F>(
+        &mut self,
+        script: &Script,
+        script_type: &str,
+        filter: F,
+    ) -> Result<Vec<LiveCell>, Error>
+    where
+        F: Fn(&CellOutput, &[u8]) -> bool,
+    {
+        let search_key = json!({
+            "script": JsonScript::from(script.clone()),
+            "script_type": script_type,
+        });
+        let limit = Uint64::from(GET_CELLS_PAGE_SIZE);
+        let mut cursor: Option<JsonBytes> = None;
+        let mut live_cells = Vec::new();
+        loop {
+            let cells: IndexerCells =
+                self.call(true, "get_cells", json!([search_key, "asc", limit, cursor]))?;
+            if cells.objects.is_empty() {
+                break;
+            }
+            for cell in cells.objects {
+                let output = CellOutput::from(cell.output);
+                let data = cell.output_data.into_bytes();
+                if !filter(&output, &data) {
+                    continue;
+                }
+                live_cells.push(LiveCell {
+                    out_point: OutPoint::from(cell.out_point),
+                    output,
+                    data,
+                });
+            }
+            cursor = Some(cells.last_cursor);
+        }
+        Ok(live_cells)
+    }
+}
+
+impl ChainClient for RpcClient {
+    fn tip_header(&mut self) -> Result<HeaderView, Error> {
+        let header: JsonHeaderView = self.call(false, "get_tip_header", json!([]))?;
+        Ok(header.into())
+    }
+
+    fn median_time(&mut self) -> Result<u64, Error> {
+        let chain_info: ChainInfo = self.call(false, "get_blockchain_info", json!([]))?;
+        Ok(chain_info.median_time.value())
+    }
+
+    fn live_cells(&mut self, type_script: &Script) -> Result<Vec<LiveCell>, Error> {
+        self.search_cells(type_script, "type", |output, _| {
+            match output.type_().to_opt() {
+                Some(output_type) => output_type.as_slice() == type_script.as_slice(),
+                None => false,
+            }
+        })
+    }
+
+    fn wallet_cells(&mut self, lock_script: &Script) -> Result<Vec<LiveCell>, Error> {
+        self.search_cells(lock_script, "lock", |output, data| {
+            output.lock().as_slice() == lock_script.as_slice()
+                && output.type_().is_none()
+                && data.is_empty()
+        })
+    }
+
+    fn header_by_number(&mut self, number: u64) -> Result<HeaderView, Error> {
+        let header: Option<JsonHeaderView> = self.call(
+            false,
+            "get_header_by_number",
+            json!([BlockNumber::from(number)]),
+        )?;
+        header
+            .map(HeaderView::from)
+            .ok_or_else(|| Error::Client(format!("header {} not found", number)))
+    }
+
+    fn send_transaction(&mut self, tx: TransactionView) -> Result<Byte32, Error> {
+        let tx = JsonTransaction::from(tx.data());
+        let tx_hash: H256 = self.call(false, "send_transaction", json!([tx]))?;
+        Ok(tx_hash.pack())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::error::Error;
+use crate::updater::UpdaterConfig;
+use ckb_jsonrpc_types::{CellDep as JsonCellDep, Script as JsonScript};
+use ckb_types::{
+    core::ScriptHashType,
+    packed::{CellDep, Script},
+    prelude::*,
+};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use time_oracle_sdk::{OracleCodes, ScriptCode};
+
+/// The toml config of the updater binary, and the scripts and cell deps have the json rpc format
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub ckb_rpc: String,
+    pub indexer_rpc: String,
+    /// The file of the hex private key of the secp256k1 locks of the oracle cells and the wallet
+    /// cells
+    pub privkey_path: String,
+    pub oracle_lock: JsonScript,
+    /// The lock of the cells which pay the fee of the updates
+    pub wallet_lock: JsonScript,
+    pub lock_deps: Vec<JsonCellDep>,
+    pub index_state_type_script: JsonScript,
+    pub index_state_type_dep: JsonCellDep,
+    pub info_type_script: JsonScript,
+    pub info_type_dep: JsonCellDep,
+    pub update_interval: u64,
+    pub poll_interval_secs: u64,
+    pub fee: u64,
+}
+
+impl Config {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let content = fs::read_to_string(path).map_err(|err| Error::Config(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| Error::Config(err.to_string()))
+    }
+
+    pub fn updater_config(&self) -> UpdaterConfig {
+        let index_state_type_script = Script::from(self.index_state_type_script.clone());
+        let info_type_script = Script::from(self.info_type_script.clone());
+        UpdaterConfig {
+            codes: OracleCodes {
+                index_state_type: script_code(
+                    &index_state_type_script,
+                    self.index_state_type_dep.clone(),
+                ),
+                info_type: script_code(&info_type_script, self.info_type_dep.clone()),
+            },
+            lock_deps: self.lock_deps.iter().cloned().map(CellDep::from).collect(),
+            index_state_type_script,
+            info_type_script,
+            wallet_lock: Script::from(self.wallet_lock.clone()),
+            update_interval: self.update_interval,
+            fee: self.fee,
+        }
+    }
+}
+
+fn script_code(script: &Script, cell_dep: JsonCellDep) -> ScriptCode {
+    let hash_type = if script.hash_type().as_slice()[0] == ScriptHashType::Type as u8 {
+        ScriptHashType::Type
+    } else {
+        ScriptHashType::Data
+    };
+    ScriptCode {
+        code_hash: script.code_hash(),
+        hash_type,
+        cell_dep: CellDep::from(cell_dep),
+    }
+}
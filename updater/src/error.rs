@@ -0,0 +1,63 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // The errors of the chain client, such as the rpc errors
+    Client(String),
+    Config(String),
+    TimeCell(time_cell_types::Error),
+    Sdk(time_oracle_sdk::Error),
+    IndexStateNotFound,
+    IndexStateDuplicated,
+    // No live time info cell has the next index of the time index state cell
+    InfoCellNotFound,
+    InvalidSignature,
+    // No live cell of the wallet lock without type script and data
+    WalletCellNotFound,
+    // The wallet cells can't pay the fee and the refilled capacity
+    WalletCapacityNotEnough,
+    // The operators of the multi-sig info type args can't be signed by the updater
+    MultiSigNotSupported,
+    // Neither the oracle lock nor the wallet lock is one of the updaters of the time index state
+    UpdaterNotAuthorized,
+}
+
+impl From<time_cell_types::Error> for Error {
+    fn from(err: time_cell_types::Error) -> Self {
+        Error::TimeCell(err)
+    }
+}
+
+impl From<time_oracle_sdk::Error> for Error {
+    fn from(err: time_oracle_sdk::Error) -> Self {
+        Error::Sdk(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Client(message) => write!(f, "chain client error: {}", message),
+            Error::Config(message) => write!(f, "invalid config: {}", message),
+            Error::TimeCell(err) => write!(f, "{}", err),
+            Error::Sdk(err) => write!(f, "{}", err),
+            Error::IndexStateNotFound => write!(f, "time index state cell not found"),
+            Error::IndexStateDuplicated => write!(f, "time index state cell duplicated"),
+            Error::InfoCellNotFound => write!(f, "time info cell of the next index not found"),
+            Error::InvalidSignature => write!(f, "invalid signature"),
+            Error::WalletCellNotFound => write!(f, "wallet cell not found"),
+            Error::WalletCapacityNotEnough => write!(f, "wallet capacity not enough"),
+            Error::MultiSigNotSupported => {
+                write!(f, "multi-sig time info cells are not supported")
+            }
+            Error::UpdaterNotAuthorized => {
+                write!(
+                    f,
+                    "neither the oracle lock nor the wallet lock is an updater"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,14 @@
+//! The updater of the time oracle, which watches the tip headers of a chain source and rotates
+//! the time index state cell and the time info cell of the next slot. The chain source is
+//! pluggable with the ChainClient trait.
+mod client;
+mod config;
+mod error;
+mod signer;
+mod updater;
+
+pub use client::{ChainClient, RpcClient};
+pub use config::Config;
+pub use error::Error;
+pub use signer::{Secp256k1Signer, TxSigner};
+pub use updater::{Updater, UpdaterConfig};
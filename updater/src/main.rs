@@ -0,0 +1,46 @@
+use ckb_crypto::secp::Privkey;
+use ckb_types::packed::Script;
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+use time_oracle_updater::{Config, Error, RpcClient, Secp256k1Signer, Updater};
+
+fn main() {
+    let config_path = match env::args().nth(1) {
+        Some(config_path) => config_path,
+        None => {
+            eprintln!("usage: ckb-time-updater <config.toml>");
+            process::exit(1);
+        }
+    };
+    if let Err(err) = run(&config_path) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn run(config_path: &str) -> Result<(), Error> {
+    let config = Config::load(config_path)?;
+    let privkey = load_privkey(&config.privkey_path)?;
+    let locks = [
+        Script::from(config.oracle_lock.clone()),
+        Script::from(config.wallet_lock.clone()),
+    ];
+    let signer = Secp256k1Signer::new(privkey, &locks);
+    let client = RpcClient::new(&config.ckb_rpc, &config.indexer_rpc);
+    let mut updater = Updater::new(client, signer, config.updater_config());
+    updater.check_oracle()?;
+    updater.run(Duration::from_secs(config.poll_interval_secs));
+    Ok(())
+}
+
+fn load_privkey(privkey_path: &str) -> Result<Privkey, Error> {
+    let content = fs::read_to_string(privkey_path).map_err(|err| Error::Config(err.to_string()))?;
+    let privkey_hex = content.trim().trim_start_matches("0x");
+    let privkey_bytes = hex::decode(privkey_hex).map_err(|err| Error::Config(err.to_string()))?;
+    if privkey_bytes.len() != 32 {
+        return Err(Error::Config("invalid private key length".to_string()));
+    }
+    Ok(Privkey::from_slice(&privkey_bytes))
+}
@@ -0,0 +1,122 @@
+use crate::error::Error;
+use ckb_crypto::secp::Privkey;
+use ckb_types::{
+    bytes::Bytes,
+    core::TransactionView,
+    packed::{self, Byte32, Script, WitnessArgs},
+    prelude::*,
+    H256,
+};
+use time_cell_types::new_blake2b;
+use time_oracle_sdk::LiveCell;
+
+const SIGNATURE_LEN: usize = 65;
+
+/// The signer of the locks of the update transaction, and the inputs are the live cells of the
+/// inputs of the transaction in order
+pub trait TxSigner {
+    fn sign(&self, tx: TransactionView, inputs: &[LiveCell]) -> Result<TransactionView, Error>;
+}
+
+/// The signer of the secp256k1 sighash all lock, which signs the input groups of the locks of
+/// the signer, such as the oracle lock and the wallet lock
+pub struct Secp256k1Signer {
+    privkey: Privkey,
+    lock_hashes: Vec<Byte32>,
+}
+
+impl Secp256k1Signer {
+    pub fn new(privkey: Privkey, locks: &[Script]) -> Self {
+        let mut lock_hashes: Vec<Byte32> = Vec::new();
+        for lock_hash in locks.iter().map(|lock| lock.calc_script_hash()) {
+            if !lock_hashes
+                .iter()
+                .any(|other| other.as_slice() == lock_hash.as_slice())
+            {
+                lock_hashes.push(lock_hash);
+            }
+        }
+        Secp256k1Signer {
+            privkey,
+            lock_hashes,
+        }
+    }
+
+    // The message is blake2b(tx hash | the first witness of the group with the zero lock | the
+    // other witnesses of the group | the witnesses without inputs), and every witness is prefixed
+    // with its length(u64, little endian)
+    fn sign_group(
+        &self,
+        tx: &TransactionView,
+        group: &[usize],
+        inputs_len: usize,
+        witnesses: &mut [packed::Bytes],
+    ) -> Result<(), Error> {
+        let first_position = group[0];
+        let first_witness = witnesses[first_position].raw_data();
+        let witness_args = if first_witness.is_empty() {
+            WitnessArgs::default()
+        } else {
+            WitnessArgs::from_slice(&first_witness).map_err(|_| Error::InvalidSignature)?
+        };
+        let zero_lock_witness = witness_args
+            .clone()
+            .as_builder()
+            .lock(Some(Bytes::from(vec![0u8; SIGNATURE_LEN])).pack())
+            .build()
+            .as_bytes();
+
+        let mut blake2b = new_blake2b();
+        blake2b.update(tx.hash().as_slice());
+        blake2b.update(&(zero_lock_witness.len() as u64).to_le_bytes());
+        blake2b.update(&zero_lock_witness);
+        let other_positions = group[1..]
+            .iter()
+            .cloned()
+            .chain(inputs_len..witnesses.len());
+        for position in other_positions {
+            let witness = witnesses[position].raw_data();
+            blake2b.update(&(witness.len() as u64).to_le_bytes());
+            blake2b.update(&witness);
+        }
+        let mut message = [0u8; 32];
+        blake2b.finalize(&mut message);
+
+        let signature = self
+            .privkey
+            .sign_recoverable(&H256::from(message))
+            .map_err(|_| Error::InvalidSignature)?;
+        witnesses[first_position] = witness_args
+            .as_builder()
+            .lock(Some(Bytes::from(signature.serialize())).pack())
+            .build()
+            .as_bytes()
+            .pack();
+        Ok(())
+    }
+}
+
+impl TxSigner for Secp256k1Signer {
+    // The signature of a group only changes the first witness of the group, so the groups are
+    // signed one by one
+    fn sign(&self, tx: TransactionView, inputs: &[LiveCell]) -> Result<TransactionView, Error> {
+        let mut witnesses: Vec<_> = tx.witnesses().into_iter().collect();
+        while witnesses.len() < inputs.len() {
+            witnesses.push(Bytes::new().pack());
+        }
+        for lock_hash in self.lock_hashes.iter() {
+            let group: Vec<usize> = inputs
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| {
+                    cell.output.lock().calc_script_hash().as_slice() == lock_hash.as_slice()
+                })
+                .map(|(position, _)| position)
+                .collect();
+            if !group.is_empty() {
+                self.sign_group(&tx, &group, inputs.len(), &mut witnesses)?;
+            }
+        }
+        Ok(tx.as_advanced_builder().set_witnesses(witnesses).build())
+    }
+}
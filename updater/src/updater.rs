@@ -0,0 +1,299 @@
+use crate::client::ChainClient;
+use crate::error::Error;
+use crate::signer::TxSigner;
+use ckb_types::{
+    bytes::Bytes,
+    core::{HeaderView, TransactionView},
+    packed::{Byte32, CellDep, Script},
+    prelude::*,
+};
+use std::thread;
+use std::time::Duration;
+use time_cell_types::{
+    epoch_with_fraction, rules::RuleError, unpack_epoch, IndexStateData, InfoTypeArgs,
+    TimeInfoData, TimeValue, MILLIS_PER_SECOND,
+};
+use time_oracle_sdk::{Error as SdkError, LiveCell, OracleCodes, UpdateTx};
+
+#[derive(Debug, Clone)]
+pub struct UpdaterConfig {
+    pub codes: OracleCodes,
+    pub lock_deps: Vec<CellDep>,
+    pub index_state_type_script: Script,
+    pub info_type_script: Script,
+    /// The lock of the wallet cells which pay the fee and take the change, and the signer signs
+    /// it as well as the lock of the oracle cells
+    pub wallet_lock: Script,
+    /// The number of blocks between two updates
+    pub update_interval: u64,
+    pub fee: u64,
+}
+
+/// The updater watches the tip of the chain and rotates the oracle to the next slot with the
+/// time of the tip every update_interval blocks. After an outage longer than the max_step of the
+/// info type args, the oracle catches up with the tip by max_step every update.
+///
+/// The operators of the multi-sig info type args can't be signed by the updater, so these
+/// oracles are rejected, and the oracle with updaters is only updated when the oracle lock or
+/// the wallet lock is one of the updaters.
+pub struct Updater<C, S> {
+    client: C,
+    signer: S,
+    config: UpdaterConfig,
+    last_update_number: Option<u64>,
+}
+
+impl<C: ChainClient, S: TxSigner> Updater<C, S> {
+    pub fn new(client: C, signer: S, config: UpdaterConfig) -> Self {
+        Updater {
+            client,
+            signer,
+            config,
+            last_update_number: None,
+        }
+    }
+
+    pub fn config(&self) -> &UpdaterConfig {
+        &self.config
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    pub fn client_mut(&mut self) -> &mut C {
+        &mut self.client
+    }
+
+    /// Check that the oracle can be updated by the updater, which is called before running the
+    /// updater and before every update
+    pub fn check_oracle(&mut self) -> Result<(), Error> {
+        let index_state_cell = self.load_index_state_cell()?;
+        let index_state = IndexStateData::from_slice(&index_state_cell.data)?;
+        self.check_oracle_mode(&index_state_cell, &index_state)
+    }
+
+    /// Poll the tip once and send the update transaction if the oracle should be updated, and
+    /// the hash of the sent transaction is returned
+    pub fn poll(&mut self) -> Result<Option<Byte32>, Error> {
+        let tip = self.client.tip_header()?;
+        if let Some(last_update_number) = self.last_update_number {
+            if tip.number() < last_update_number + self.config.update_interval {
+                return Ok(None);
+            }
+        }
+
+        let index_state_cell = self.load_index_state_cell()?;
+        let index_state = IndexStateData::from_slice(&index_state_cell.data)?;
+        let info_type_args = self.check_oracle_mode(&index_state_cell, &index_state)?;
+        let info_cells = self.client.live_cells(&self.config.info_type_script)?;
+        let (latest_info_cell, latest_info) = find_info(&info_cells, index_state.index)?;
+        let (next_info_cell, next_info) = find_info(&info_cells, index_state.next_index())?;
+
+        let latest_value = latest_info.value();
+        let (time, header_dep) = if info_type_args.has_header_dep_proof() {
+            let header =
+                self.find_stepped_header(&tip, &next_info, &latest_value, info_type_args.max_step)?;
+            (header_time(&header, &next_info), Some(header.hash()))
+        } else {
+            let time = self.load_tip_time(&tip, &next_info)?;
+            (
+                clamp_time(time, &latest_value, info_type_args.max_step),
+                None,
+            )
+        };
+
+        // The wallet cells are added one by one until they pay the fee and the refilled capacity,
+        // and the oracle is not updated until the time passes the rules of the info type, such
+        // as the time bigger than the latest time and the step not smaller than min_step
+        let mut wallet_cells = self
+            .client
+            .wallet_cells(&self.config.wallet_lock)?
+            .into_iter();
+        let mut extra_inputs = vec![wallet_cells.next().ok_or(Error::WalletCellNotFound)?];
+        let tx = loop {
+            let update_tx = UpdateTx {
+                codes: self.config.codes.clone(),
+                lock_deps: self.config.lock_deps.clone(),
+                index_state_cell: index_state_cell.clone(),
+                info_cell: next_info_cell.clone(),
+                latest_info_cell: latest_info_cell.clone(),
+                time,
+                header_dep: header_dep.clone(),
+                extra_inputs: extra_inputs.clone(),
+                change_lock: self.config.wallet_lock.clone(),
+                fee: self.config.fee,
+            };
+            match update_tx.build() {
+                Ok(tx) => break tx,
+                Err(SdkError::InsufficientCapacity) => match wallet_cells.next() {
+                    Some(wallet_cell) => extra_inputs.push(wallet_cell),
+                    None => return Err(Error::WalletCapacityNotEnough),
+                },
+                Err(SdkError::Rule(RuleError::TimeNotBigger))
+                | Err(SdkError::Rule(RuleError::TimeInfoStepTooSmall)) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            }
+        };
+        let tx_hash = self.sign_and_send(tx, index_state_cell, next_info_cell, extra_inputs)?;
+        self.last_update_number = Some(tip.number());
+        Ok(Some(tx_hash))
+    }
+
+    /// Poll the tip forever, and the errors are printed without stopping the updater
+    pub fn run(&mut self, poll_interval: Duration) {
+        loop {
+            match self.poll() {
+                Ok(Some(tx_hash)) => println!("oracle updated: {}", tx_hash),
+                Ok(None) => {}
+                Err(err) => eprintln!("oracle update failed: {}", err),
+            }
+            thread::sleep(poll_interval);
+        }
+    }
+
+    fn sign_and_send(
+        &mut self,
+        tx: TransactionView,
+        index_state_cell: LiveCell,
+        info_cell: LiveCell,
+        extra_inputs: Vec<LiveCell>,
+    ) -> Result<Byte32, Error> {
+        let mut inputs = vec![index_state_cell, info_cell];
+        inputs.extend(extra_inputs);
+        let tx = self.signer.sign(tx, &inputs)?;
+        self.client.send_transaction(tx)
+    }
+
+    // The multi-sig oracle is rejected, and the oracle with updaters is rejected unless the
+    // oracle lock or the wallet lock, which are the locks of the inputs, is one of the updaters
+    fn check_oracle_mode(
+        &self,
+        index_state_cell: &LiveCell,
+        index_state: &IndexStateData,
+    ) -> Result<InfoTypeArgs, Error> {
+        let info_args: Bytes = self.config.info_type_script.args().unpack();
+        let info_type_args = InfoTypeArgs::from_slice(&info_args)?;
+        if info_type_args.has_multi_sig() {
+            return Err(Error::MultiSigNotSupported);
+        }
+        let is_authorized = [&index_state_cell.output.lock(), &self.config.wallet_lock]
+            .iter()
+            .any(|lock| {
+                let mut lock_hash = [0u8; 32];
+                lock_hash.copy_from_slice(lock.calc_script_hash().as_slice());
+                index_state.is_updater_authorized(&lock_hash)
+            });
+        if !is_authorized {
+            return Err(Error::UpdaterNotAuthorized);
+        }
+        Ok(info_type_args)
+    }
+
+    fn load_index_state_cell(&mut self) -> Result<LiveCell, Error> {
+        let mut index_state_cells = self
+            .client
+            .live_cells(&self.config.index_state_type_script)?;
+        match index_state_cells.len() {
+            0 => Err(Error::IndexStateNotFound),
+            1 => Ok(index_state_cells.remove(0)),
+            _ => Err(Error::IndexStateDuplicated),
+        }
+    }
+
+    // The time of the tip has the same kind and layout as the time info cell to be overwritten,
+    // and the timestamp is the median time which the since is compared with
+    fn load_tip_time(&mut self, tip: &HeaderView, info: &TimeInfoData) -> Result<TimeValue, Error> {
+        match info.value() {
+            TimeValue::Timestamp(_) | TimeValue::TimestampMillis(_) => {
+                Ok(timestamp_time(self.client.median_time()?, info))
+            }
+            _ => Ok(header_time(tip, info)),
+        }
+    }
+
+    // The header dep must prove the written time, so the time can't be clamped. Instead the
+    // header is searched by the block number for the latest one whose time is within max_step
+    // after the latest time.
+    fn find_stepped_header(
+        &mut self,
+        tip: &HeaderView,
+        info: &TimeInfoData,
+        latest_value: &TimeValue,
+        max_step: u64,
+    ) -> Result<HeaderView, Error> {
+        let is_within_max_step = |header: &HeaderView| {
+            clamp_time(header_time(header, info), latest_value, max_step)
+                == header_time(header, info)
+        };
+        if is_within_max_step(tip) {
+            return Ok(tip.clone());
+        }
+        let (mut low, mut high) = (0, tip.number());
+        let mut header = self.client.header_by_number(low)?;
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            let middle_header = self.client.header_by_number(middle)?;
+            if is_within_max_step(&middle_header) {
+                low = middle;
+                header = middle_header;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(header)
+    }
+}
+
+// The time of the header in the kind and layout of the time info
+fn header_time(header: &HeaderView, info: &TimeInfoData) -> TimeValue {
+    match info.value() {
+        TimeValue::BlockNumber(_) => TimeValue::BlockNumber(header.number()),
+        TimeValue::Epoch(_) => TimeValue::Epoch(header.epoch().full_value()),
+        _ => timestamp_time(header.timestamp(), info),
+    }
+}
+
+fn timestamp_time(timestamp: u64, info: &TimeInfoData) -> TimeValue {
+    match info.value() {
+        TimeValue::Timestamp(_) => TimeValue::Timestamp((timestamp / MILLIS_PER_SECOND) as u32),
+        _ => TimeValue::TimestampMillis(timestamp),
+    }
+}
+
+// The time more than max_step after the latest time is clamped to max_step after the latest
+// time, and the step of the timestamp is measured in the unit of the versioned layout. The
+// clamped time is earlier than the time of the tip, so it is still proved by the since.
+fn clamp_time(time: TimeValue, latest_value: &TimeValue, max_step: u64) -> TimeValue {
+    let latest_value = latest_value.to_versioned();
+    if max_step == 0 || time.to_versioned().step_from(&latest_value) <= max_step {
+        return time;
+    }
+    match (time, latest_value) {
+        (TimeValue::Timestamp(_), TimeValue::TimestampMillis(latest)) => {
+            TimeValue::Timestamp(((latest + max_step) / MILLIS_PER_SECOND) as u32)
+        }
+        (TimeValue::TimestampMillis(_), TimeValue::TimestampMillis(latest)) => {
+            TimeValue::TimestampMillis(latest + max_step)
+        }
+        (TimeValue::BlockNumber(_), TimeValue::BlockNumber(latest)) => {
+            TimeValue::BlockNumber(latest + max_step)
+        }
+        (TimeValue::Epoch(_), TimeValue::Epoch(latest)) => {
+            let (number, _, _) = unpack_epoch(latest);
+            TimeValue::Epoch(epoch_with_fraction(number + max_step, 0, 1))
+        }
+        _ => time,
+    }
+}
+
+fn find_info(info_cells: &[LiveCell], index: u8) -> Result<(LiveCell, TimeInfoData), Error> {
+    for cell in info_cells {
+        if let Ok(info) = TimeInfoData::from_slice(&cell.data) {
+            if info.index() == index {
+                return Ok((cell.clone(), info));
+            }
+        }
+    }
+    Err(Error::InfoCellNotFound)
+}